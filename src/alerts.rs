@@ -0,0 +1,220 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::crypto_coin::{CoinId, CryptoCoin};
+
+/// Oldest notifications are dropped past this many.
+pub const MAX_NOTIFICATIONS: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AlertRule {
+    PriceAbove(f64),
+    PriceBelow(f64),
+    /// Fires when the price moved more than this percent over the last day.
+    DailyChange(f64),
+    RugSignal,
+}
+
+impl AlertRule {
+    pub fn label(&self) -> String {
+        match self {
+            AlertRule::PriceAbove(price) => format!("Price above ${price:.2}"),
+            AlertRule::PriceBelow(price) => format!("Price below ${price:.2}"),
+            AlertRule::DailyChange(percent) => format!("Daily move over {percent:.0}%"),
+            AlertRule::RugSignal => "Rug warning sign".to_string(),
+        }
+    }
+
+    /// Name used by the rule picker, the value is entered separately.
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            AlertRule::PriceAbove(_) => "Price above",
+            AlertRule::PriceBelow(_) => "Price below",
+            AlertRule::DailyChange(_) => "Daily change %",
+            AlertRule::RugSignal => "Rug signal",
+        }
+    }
+
+    /// The rule of the same kind with a new value.
+    pub fn with_value(&self, value: f64) -> AlertRule {
+        match self {
+            AlertRule::PriceAbove(_) => AlertRule::PriceAbove(value),
+            AlertRule::PriceBelow(_) => AlertRule::PriceBelow(value),
+            AlertRule::DailyChange(_) => AlertRule::DailyChange(value),
+            AlertRule::RugSignal => AlertRule::RugSignal,
+        }
+    }
+
+    pub fn all() -> Vec<AlertRule> {
+        vec![
+            AlertRule::PriceAbove(0.0),
+            AlertRule::PriceBelow(0.0),
+            AlertRule::DailyChange(0.0),
+            AlertRule::RugSignal,
+        ]
+    }
+
+    /// Whether the rule's condition holds for the coin right now.
+    fn matches(&self, coin: &CryptoCoin, due_diligence: u64) -> bool {
+        match self {
+            AlertRule::PriceAbove(price) => coin.current_price > *price,
+            AlertRule::PriceBelow(price) => coin.current_price < *price,
+            AlertRule::DailyChange(percent) => daily_change(coin).abs() * 100.0 > *percent,
+            AlertRule::RugSignal => !coin.rug_signals(due_diligence).is_empty(),
+        }
+    }
+}
+
+/// Price change over the coin's price history, which covers one game day.
+pub fn daily_change(coin: &CryptoCoin) -> f64 {
+    match coin.prices.first() {
+        Some(first) if *first > 0.0 => coin.current_price / first - 1.0,
+        _ => 0.0,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Alert {
+    pub id: u64,
+    pub coin: CoinId,
+    pub coin_name: String,
+    pub rule: AlertRule,
+    /// Set while the condition holds so an alert fires once per crossing.
+    pub armed: bool,
+    pub fired: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Notification {
+    pub day: u64,
+    pub message: String,
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AlertCenter {
+    pub watchlist: Vec<CoinId>,
+    pub alerts: Vec<Alert>,
+    pub notifications: Vec<Notification>,
+    pub next_id: u64,
+    pub browser_notifications: bool,
+}
+
+impl AlertCenter {
+    pub fn new() -> Self {
+        AlertCenter {
+            watchlist: Vec::new(),
+            alerts: Vec::new(),
+            notifications: Vec::new(),
+            next_id: 0,
+            browser_notifications: false,
+        }
+    }
+
+    pub fn is_watched(&self, coin: CoinId) -> bool {
+        self.watchlist.contains(&coin)
+    }
+
+    pub fn toggle_watch(&mut self, coin: CoinId) {
+        if self.is_watched(coin) {
+            self.watchlist.retain(|c| *c != coin);
+        } else {
+            self.watchlist.push(coin);
+        }
+    }
+
+    pub fn add_alert(&mut self, coin: &CryptoCoin, rule: AlertRule) {
+        self.alerts.push(Alert {
+            id: self.next_id,
+            coin: coin.id(),
+            coin_name: coin.name.clone(),
+            rule,
+            armed: true,
+            fired: 0,
+        });
+        self.next_id += 1;
+
+        if !self.is_watched(coin.id()) {
+            self.watchlist.push(coin.id());
+        }
+    }
+
+    pub fn remove_alert(&mut self, id: u64) {
+        self.alerts.retain(|a| a.id != id);
+    }
+
+    pub fn alerts_for(&self, coin: CoinId) -> Vec<Alert> {
+        self.alerts
+            .iter()
+            .filter(|a| a.coin == coin)
+            .cloned()
+            .collect()
+    }
+
+    /// Drops the watch and alerts on a coin that left the market.
+    pub fn forget_coin(&mut self, coin: CoinId) {
+        self.watchlist.retain(|c| *c != coin);
+        self.alerts.retain(|a| a.coin != coin);
+    }
+
+    /// Checks every alert against the coins and returns the messages of the
+    /// ones that fired. An alert fires when its condition starts to hold and
+    /// re-arms once it stops.
+    pub fn check(&mut self, coins: &[CryptoCoin], due_diligence: u64, day: u64) -> Vec<String> {
+        let mut fired = Vec::new();
+
+        for alert in self.alerts.iter_mut() {
+            let coin = match coins.iter().find(|c| c.id() == alert.coin && c.active) {
+                Some(coin) => coin,
+                None => continue,
+            };
+
+            let matches = alert.rule.matches(coin, due_diligence);
+
+            if matches && alert.armed {
+                alert.armed = false;
+                alert.fired += 1;
+                fired.push(format!(
+                    "ALERT: {} {} (now ${:.2})",
+                    coin.name,
+                    alert.rule.label().to_lowercase(),
+                    coin.current_price
+                ));
+            } else if !matches {
+                alert.armed = true;
+            }
+        }
+
+        for message in &fired {
+            self.notify(day, message);
+        }
+
+        fired
+    }
+
+    pub fn notify(&mut self, day: u64, message: &str) {
+        self.notifications.push(Notification {
+            day,
+            message: message.to_string(),
+            read: false,
+        });
+
+        while self.notifications.len() > MAX_NOTIFICATIONS {
+            self.notifications.remove(0);
+        }
+    }
+
+    pub fn unread(&self) -> usize {
+        self.notifications.iter().filter(|n| !n.read).count()
+    }
+
+    pub fn mark_all_read(&mut self) {
+        for notification in self.notifications.iter_mut() {
+            notification.read = true;
+        }
+    }
+
+    pub fn clear_notifications(&mut self) {
+        self.notifications.clear();
+    }
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+/// How the rig's hash is split between the coins being mined.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum HashAllocation {
+    Equal,
+    /// A percentage set per selection.
+    Custom,
+    /// In proportion to each coin's profit factor.
+    Profit,
+}
+
+impl HashAllocation {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HashAllocation::Equal => "Equal Split",
+            HashAllocation::Custom => "Custom %",
+            HashAllocation::Profit => "By Profit",
+        }
+    }
+
+    pub fn all() -> Vec<HashAllocation> {
+        vec![
+            HashAllocation::Equal,
+            HashAllocation::Custom,
+            HashAllocation::Profit,
+        ]
+    }
+}
+
+/// Splits the hash rate by the given weights. Negative weights count as
+/// zero, and if nothing is left the split is even.
+pub fn split_hash(hash_rate: u64, weights: &[f64]) -> Vec<u64> {
+    if weights.is_empty() {
+        return Vec::new();
+    }
+
+    let weights: Vec<f64> = weights
+        .iter()
+        .map(|w| if w.is_finite() { w.max(0.0) } else { 0.0 })
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    if total <= 0.0 {
+        let even = hash_rate / weights.len() as u64;
+        return vec![even; weights.len()];
+    }
+
+    weights
+        .iter()
+        .map(|w| (hash_rate as f64 * w / total).floor() as u64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_in_proportion_to_the_weights() {
+        assert_eq!(split_hash(100, &[1.0, 1.0]), vec![50, 50]);
+        assert_eq!(split_hash(100, &[3.0, 1.0]), vec![75, 25]);
+        assert_eq!(split_hash(100, &[75.0, 25.0, 0.0]), vec![75, 25, 0]);
+    }
+
+    #[test]
+    fn never_hands_out_more_than_the_hash_rate() {
+        let split = split_hash(100, &[1.0, 1.0, 1.0]);
+
+        assert_eq!(split, vec![33, 33, 33]);
+        assert!(split.iter().sum::<u64>() <= 100);
+    }
+
+    #[test]
+    fn ignores_negative_and_invalid_weights() {
+        assert_eq!(split_hash(90, &[-5.0, 1.0, f64::NAN]), vec![0, 90, 0]);
+        assert_eq!(split_hash(90, &[f64::INFINITY, 1.0]), vec![0, 90]);
+    }
+
+    #[test]
+    fn falls_back_to_an_even_split() {
+        assert_eq!(split_hash(90, &[0.0, 0.0, -1.0]), vec![30, 30, 30]);
+        assert!(split_hash(90, &[]).is_empty());
+    }
+}
@@ -0,0 +1,154 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::utils::rand_from_range;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CoinArchetype {
+    Stablecoin,
+    BlueChip,
+    MemeCoin,
+    PrivacyCoin,
+    ScamToken,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchetypeProfile {
+    pub archetype: CoinArchetype,
+    pub name: &'static str,
+    pub hint: &'static str,
+    pub weight: f64,
+    pub volatility: Range<f64>,
+    pub beta: Range<f64>,
+    pub amplitude: f64,
+    pub trend_bias: f64,
+    pub mean_reversion: f64,
+    pub lifespan: f64,
+    pub rug_multiplier: f64,
+    pub reward_multiplier: f64,
+    pub shares_per_block: u64,
+    pub premine: Range<f64>,
+    pub turnover: Range<f64>,
+}
+
+// Scam tokens deliberately share the meme coin hint, telling them apart is up
+// to the player.
+pub const ARCHETYPE_PROFILES: [ArchetypeProfile; 5] = [
+    ArchetypeProfile {
+        archetype: CoinArchetype::Stablecoin,
+        name: "Stablecoin",
+        hint: "Pegged",
+        weight: 0.15,
+        volatility: 0.002..0.006,
+        beta: 0.0..0.1,
+        amplitude: 0.1,
+        trend_bias: 0.0,
+        mean_reversion: 0.2,
+        lifespan: 200.0,
+        rug_multiplier: 0.3,
+        reward_multiplier: 0.5,
+        shares_per_block: 1000,
+        premine: 0.6..0.9,
+        turnover: 0.3..0.6,
+    },
+    ArchetypeProfile {
+        archetype: CoinArchetype::BlueChip,
+        name: "Blue Chip",
+        hint: "Established",
+        weight: 0.3,
+        volatility: 0.01..0.04,
+        beta: 0.8..1.2,
+        amplitude: 0.6,
+        trend_bias: 0.0005,
+        mean_reversion: 0.0,
+        lifespan: 150.0,
+        rug_multiplier: 0.5,
+        reward_multiplier: 0.8,
+        shares_per_block: 1000,
+        premine: 0.2..0.4,
+        turnover: 0.05..0.15,
+    },
+    ArchetypeProfile {
+        archetype: CoinArchetype::MemeCoin,
+        name: "Meme Coin",
+        hint: "Hyped",
+        weight: 0.25,
+        volatility: 0.05..0.12,
+        beta: 1.3..2.0,
+        amplitude: 1.3,
+        trend_bias: 0.0,
+        mean_reversion: 0.0,
+        lifespan: 60.0,
+        rug_multiplier: 1.5,
+        reward_multiplier: 1.5,
+        shares_per_block: 800,
+        premine: 0.3..0.6,
+        turnover: 0.2..0.5,
+    },
+    ArchetypeProfile {
+        archetype: CoinArchetype::PrivacyCoin,
+        name: "Privacy Coin",
+        hint: "Private",
+        weight: 0.15,
+        volatility: 0.03..0.07,
+        beta: 0.6..1.0,
+        amplitude: 0.9,
+        trend_bias: 0.0002,
+        mean_reversion: 0.0,
+        lifespan: 100.0,
+        rug_multiplier: 1.0,
+        reward_multiplier: 1.2,
+        shares_per_block: 1200,
+        premine: 0.1..0.3,
+        turnover: 0.03..0.1,
+    },
+    ArchetypeProfile {
+        archetype: CoinArchetype::ScamToken,
+        name: "Scam Token",
+        hint: "Hyped",
+        weight: 0.15,
+        volatility: 0.06..0.1,
+        beta: 1.0..1.8,
+        amplitude: 1.2,
+        trend_bias: 0.001,
+        mean_reversion: 0.0,
+        lifespan: 25.0,
+        rug_multiplier: 4.0,
+        reward_multiplier: 2.5,
+        shares_per_block: 600,
+        premine: 0.5..0.8,
+        turnover: 0.1..0.3,
+    },
+];
+
+impl CoinArchetype {
+    pub fn profile(&self) -> &'static ArchetypeProfile {
+        ARCHETYPE_PROFILES
+            .iter()
+            .find(|p| p.archetype == *self)
+            .expect("every archetype should have a profile")
+    }
+
+    pub fn random() -> Self {
+        let total_weight: f64 = ARCHETYPE_PROFILES.iter().map(|p| p.weight).sum();
+        let mut roll = rand_from_range(0.0..total_weight);
+
+        for profile in ARCHETYPE_PROFILES.iter() {
+            if roll < profile.weight {
+                return profile.archetype;
+            }
+            roll -= profile.weight;
+        }
+
+        CoinArchetype::BlueChip
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.profile().name
+    }
+
+    pub fn hint(&self) -> &'static str {
+        self.profile().hint
+    }
+}
@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::crypto_coin::{CoinId, CryptoCoin};
+
+pub const MAX_AUTO_MINING_LEVEL: u64 = 5;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct AutoMiner {
+    pub active: bool,
+    /// Skip coins showing rug warning signs and weigh the rest by rug chance.
+    pub avoid_rug_risk: bool,
+    /// Favour coins with more of their blocks left to mine.
+    pub prefer_blocks_left: bool,
+    pub updates_since_rank: u64,
+}
+
+impl AutoMiner {
+    pub fn new() -> Self {
+        AutoMiner {
+            active: true,
+            avoid_rug_risk: false,
+            prefer_blocks_left: false,
+            updates_since_rank: 0,
+        }
+    }
+
+    /// Counts a market update and returns true when it is time to re-rank.
+    pub fn tick(&mut self, level: u64) -> bool {
+        self.updates_since_rank += 1;
+
+        if self.updates_since_rank >= rerank_interval(level) {
+            self.updates_since_rank = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Coins worth mining best first, at most `slots` of them. Every coin is
+    /// scored at the same `hash_rate` so the current split can't favour the
+    /// coins already being mined.
+    pub fn rank(
+        &self,
+        coins: &[CryptoCoin],
+        due_diligence: u64,
+        slots: usize,
+        hash_rate: u64,
+    ) -> Vec<CoinId> {
+        let mut scored: Vec<(CoinId, f64)> = coins
+            .iter()
+            .filter(|c| c.active && c.blocks < c.max_blocks)
+            .filter(|c| !self.avoid_rug_risk || c.rug_signals(due_diligence).is_empty())
+            .map(|c| (c.id(), self.score(c, hash_rate)))
+            .collect();
+
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored.into_iter().take(slots).map(|(id, _)| id).collect()
+    }
+
+    fn score(&self, coin: &CryptoCoin, hash_rate: u64) -> f64 {
+        let mut score = coin.calculate_profit_factor(hash_rate);
+
+        if self.avoid_rug_risk {
+            score *= (1.0 - coin.calculate_rug_chance() * 10.0).max(0.0);
+        }
+
+        if self.prefer_blocks_left {
+            let left = 1.0 - coin.blocks as f64 / coin.max_blocks.max(1) as f64;
+            score *= 0.5 + left * 0.5;
+        }
+
+        score
+    }
+}
+
+/// Market updates between re-ranks, from every four game hours at level one
+/// down to every update at the top level.
+pub fn rerank_interval(level: u64) -> u64 {
+    match level {
+        0 | 1 => 16,
+        2 => 8,
+        3 => 4,
+        4 => 2,
+        _ => 1,
+    }
+}
+
+/// Number of mining slots the auto-miner fills, limited by multi-mining.
+pub fn auto_mining_slots(level: u64, max_selectable: u8) -> usize {
+    (level as usize).min(max_selectable as usize).max(1)
+}
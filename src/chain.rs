@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::market::MAX_SERIES_LENGTH;
+use crate::utils::rand_from_range;
+
+/// Blocks between difficulty retargets.
+pub const RETARGET_INTERVAL: u64 = 5;
+/// A retarget moves difficulty by at most this factor either way.
+pub const MAX_RETARGET_STEP: f64 = 2.0;
+/// Between retargets difficulty follows the network by at most this factor
+/// per market update, so coins nobody mines still adjust.
+pub const MAX_UPDATE_STEP: f64 = 1.05;
+/// Number of reward halvings spread over a coin's `max_blocks`.
+pub const HALVINGS: u64 = 3;
+/// Time between blocks across a coin's whole network. Difficulty retargets
+/// keep it steady, so it does not depend on how much of the hash is yours.
+pub const NETWORK_BLOCK_SECONDS: f64 = 10.0;
+/// Game days rivals holding all of a coin's hash take to mine out its
+/// blocks, so small early coins last as long as big ones.
+pub const RIVAL_SUPPLY_DAYS: f64 = 20.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainState {
+    pub difficulty: f64,
+    pub base_difficulty: f64,
+    pub network_hash: f64,
+    pub reference_hash: f64,
+    pub player_hash: f64,
+    pub last_retarget_block: u64,
+    pub retargets: u64,
+    /// Hash from rival NPC miners on the coin.
+    pub rival_hash: Option<f64>,
+}
+
+impl ChainState {
+    /// Starts from the coin's current difficulty so older saves don't jump,
+    /// the simulated network starts out about as strong as the rig mining it.
+    pub fn new(difficulty: f64, rig_hash: f64) -> Self {
+        let network_hash = rig_hash.max(1000.0) * rand_from_range(0.5..2.0);
+        let base_difficulty = difficulty;
+
+        ChainState {
+            difficulty: base_difficulty,
+            base_difficulty,
+            network_hash,
+            reference_hash: network_hash,
+            player_hash: 0.0,
+            last_retarget_block: 0,
+            retargets: 0,
+            rival_hash: None,
+        }
+    }
+
+    pub fn total_hash(&self) -> f64 {
+        self.network_hash + self.player_hash + self.rival_hash.unwrap_or(0.0)
+    }
+
+    /// The player's share of the hash on the chain.
+    pub fn player_share(&self) -> f64 {
+        if self.total_hash() <= 0.0 {
+            return 0.0;
+        }
+        self.player_hash / self.total_hash()
+    }
+
+    /// The rival miners' share of the hash on the chain.
+    pub fn rival_share(&self) -> f64 {
+        if self.total_hash() <= 0.0 {
+            return 0.0;
+        }
+        self.rival_hash.unwrap_or(0.0) / self.total_hash()
+    }
+
+    /// Blocks rivals are expected to win between two market updates, paced
+    /// by the coin's supply of blocks over game days.
+    pub fn rival_blocks_expected(&self, max_blocks: u64) -> f64 {
+        let per_update = max_blocks as f64 / RIVAL_SUPPLY_DAYS / MAX_SERIES_LENGTH as f64;
+        per_update * self.rival_share()
+    }
+
+    pub fn set_rival_hash(&mut self, hash_rate: f64) {
+        self.rival_hash = Some(hash_rate);
+    }
+
+    pub fn record_player_hash(&mut self, hash_rate: f64) {
+        self.player_hash = self.player_hash.max(hash_rate);
+    }
+
+    /// Moves the simulated network once per market update. Miners follow the
+    /// price, so the network grows while a coin pumps and leaves as it dumps.
+    pub fn step(&mut self, price_ratio: f64) {
+        let target = self.reference_hash * price_ratio.clamp(0.1, 10.0);
+        let drift = (target - self.network_hash) * 0.05;
+
+        self.network_hash = (self.network_hash + drift) * (1.0 + rand_from_range(-0.02..0.02));
+        self.network_hash = self.network_hash.max(100.0);
+
+        // Player hash fades once they stop mining the coin
+        self.player_hash *= 0.5;
+
+        let previous = self.difficulty;
+        self.difficulty = self
+            .target_difficulty()
+            .clamp(previous / MAX_UPDATE_STEP, previous * MAX_UPDATE_STEP);
+    }
+
+    /// The difficulty that matches the hash on the chain right now.
+    fn target_difficulty(&self) -> f64 {
+        let ratio = self.total_hash() / self.reference_hash.max(1.0);
+        self.base_difficulty * ratio
+    }
+
+    /// Retargets difficulty once enough blocks have passed. Returns the new
+    /// difficulty when it changed.
+    pub fn retarget(&mut self, blocks: u64) -> Option<f64> {
+        if blocks < self.last_retarget_block + RETARGET_INTERVAL {
+            return None;
+        }
+
+        let target = self.target_difficulty();
+        let previous = self.difficulty;
+
+        self.difficulty = target.clamp(previous / MAX_RETARGET_STEP, previous * MAX_RETARGET_STEP);
+        self.last_retarget_block = blocks;
+        self.retargets += 1;
+
+        Some(self.difficulty)
+    }
+}
+
+/// Blocks between halvings for a coin with the given supply of blocks.
+pub fn halving_interval(max_blocks: u64) -> u64 {
+    (max_blocks / (HALVINGS + 1)).max(1)
+}
+
+/// Number of halvings that have happened by the given block height.
+pub fn halvings_at(blocks: u64, max_blocks: u64) -> u64 {
+    (blocks / halving_interval(max_blocks)).min(HALVINGS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chain(rival_share: f64) -> ChainState {
+        ChainState {
+            difficulty: 1.0,
+            base_difficulty: 1.0,
+            network_hash: 1000.0 * (1.0 - rival_share),
+            reference_hash: 1000.0,
+            player_hash: 0.0,
+            last_retarget_block: 0,
+            retargets: 0,
+            rival_hash: Some(1000.0 * rival_share),
+        }
+    }
+
+    /// Game days until rivals alone mine out the coin's blocks.
+    fn rival_lifetime_days(chain: &ChainState, max_blocks: u64) -> f64 {
+        let per_day = chain.rival_blocks_expected(max_blocks) * MAX_SERIES_LENGTH as f64;
+        max_blocks as f64 / per_day
+    }
+
+    #[test]
+    fn early_coins_outlast_a_game_day_of_rival_mining() {
+        let chain = chain(0.5);
+
+        for max_blocks in [10, 25, 500] {
+            let days = rival_lifetime_days(&chain, max_blocks);
+            assert!(
+                (30.0..=50.0).contains(&days),
+                "{max_blocks} blocks last {days} days"
+            );
+        }
+    }
+
+    #[test]
+    fn full_rival_hash_mines_out_in_the_supply_days() {
+        let days = rival_lifetime_days(&chain(1.0), 25);
+
+        assert!((days - RIVAL_SUPPLY_DAYS).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_rivals_mine_no_blocks() {
+        let mut chain = chain(0.0);
+        chain.rival_hash = None;
+
+        assert_eq!(chain.rival_blocks_expected(25), 0.0);
+    }
+}
@@ -0,0 +1,250 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::archetype::CoinArchetype;
+use crate::utils::rand_from_range;
+
+const PREFIXES: [&str; 24] = [
+    "Zor", "Lum", "Qua", "Nex", "Vol", "Kry", "Aet", "Xen", "Flu", "Gal", "Sol", "Ori", "Vex",
+    "Myr", "Tal", "Cyn", "Ard", "Bry", "Eon", "Hal", "Ion", "Jun", "Pyr", "Syl",
+];
+
+const SUFFIXES: [&str; 16] = [
+    "ex", "ium", "ora", "ix", "on", "ara", "ant", "yx", "is", "una", "eon", "ova", "ine", "ar",
+    "os", "ia",
+];
+
+const TAILS: [&str; 8] = ["", "", "", "Coin", "Chain", "Cash", "Net", "Bit"];
+
+const MEME_HEADS: [&str; 16] = [
+    "Doge", "Shiba", "Pepe", "Frog", "Wojak", "Cat", "Bonk", "Hamster", "Goose", "Llama", "Chad",
+    "Degen", "Banana", "Toad", "Otter", "Pug",
+];
+
+const MEME_TAILS: [&str; 10] = [
+    "Inu", "Moon", "Rocket", "Lambo", "King", "Mania", "Pump", "Verse", "Army", "Gold",
+];
+
+const FORK_TAILS: [&str; 6] = ["Classic", "Cash", "Gold", "Vision", "Plus", "Reborn"];
+
+const STABLE_HEADS: [&str; 8] = [
+    "Peg", "Anchor", "Steady", "Vault", "Reserve", "True", "Base", "Solid",
+];
+
+const STABLE_TAILS: [&str; 4] = ["USD", "Dollar", "EUR", "Cash"];
+
+const PITCH_SUBJECTS: [&str; 12] = [
+    "a decentralized",
+    "a community owned",
+    "a zero-knowledge",
+    "a lightning fast",
+    "an eco friendly",
+    "a quantum resistant",
+    "an AI powered",
+    "a fully on-chain",
+    "a cross-chain",
+    "a permissionless",
+    "a gamified",
+    "a self-governing",
+];
+
+const PITCH_NOUNS: [&str; 12] = [
+    "payment network",
+    "social token",
+    "data marketplace",
+    "lending protocol",
+    "storage layer",
+    "loyalty program",
+    "betting exchange",
+    "gaming economy",
+    "music royalty pool",
+    "identity layer",
+    "supply chain ledger",
+    "meme archive",
+];
+
+const PITCH_AUDIENCES: [&str; 12] = [
+    "gamers",
+    "pet owners",
+    "farmers",
+    "artists",
+    "small businesses",
+    "night owls",
+    "coffee lovers",
+    "space tourists",
+    "landlords",
+    "sports fans",
+    "students",
+    "everyone",
+];
+
+fn pick<'a>(list: &[&'a str]) -> &'a str {
+    list[rand_from_range(0.0..list.len() as f64) as usize]
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoinLogo {
+    pub hue: u16,
+    pub cells: u32,
+}
+
+impl CoinLogo {
+    /// A mirrored 5x5 identicon, only the left three columns are random.
+    pub fn random() -> Self {
+        CoinLogo {
+            hue: rand_from_range(0.0..360.0) as u16,
+            cells: rand_from_range(1.0..32768.0) as u32,
+        }
+    }
+
+    pub fn color(&self) -> String {
+        format!("hsl({}, 65%, 45%)", self.hue)
+    }
+
+    /// Returns the filled (x, y) cells of the 5x5 grid.
+    pub fn filled_cells(&self) -> Vec<(u32, u32)> {
+        let mut filled = Vec::new();
+
+        for y in 0..5 {
+            for x in 0..3 {
+                if self.cells & (1 << (y * 3 + x)) != 0 {
+                    filled.push((x, y));
+                    if x < 2 {
+                        filled.push((4 - x, y));
+                    }
+                }
+            }
+        }
+
+        filled
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoinIdentity {
+    pub name: String,
+    pub ticker: String,
+    pub logo: CoinLogo,
+    pub pitch: String,
+}
+
+impl CoinIdentity {
+    /// Generates a name, ticker, logo and pitch. Names already in `taken` are
+    /// rerolled, names are single words so they stay usable as element ids.
+    pub fn generate(archetype: CoinArchetype, taken: &[String]) -> Self {
+        let mut name = gen_name(archetype);
+        let mut attempts = 0;
+
+        while taken.contains(&name) && attempts < 20 {
+            name = gen_name(archetype);
+            attempts += 1;
+        }
+
+        if taken.contains(&name) {
+            let mut n = 2;
+            while taken.contains(&format!("{name}{n}")) {
+                n += 1;
+            }
+            name = format!("{name}{n}");
+        }
+
+        CoinIdentity {
+            ticker: gen_ticker(&name),
+            logo: CoinLogo::random(),
+            pitch: gen_pitch(&name),
+            name,
+        }
+    }
+
+    /// The identity of a hard fork, named after the coin it split from.
+    pub fn fork(parent: &str, parent_ticker: &str, taken: &[String]) -> Self {
+        let mut tail = pick(&FORK_TAILS);
+        let mut attempts = 0;
+
+        while taken.contains(&format!("{parent}{tail}")) && attempts < 20 {
+            tail = pick(&FORK_TAILS);
+            attempts += 1;
+        }
+
+        let mut name = format!("{parent}{tail}");
+        let mut n = 2;
+        while taken.contains(&name) {
+            name = format!("{parent}{tail}{n}");
+            n += 1;
+        }
+
+        let ticker = format!(
+            "{}{}",
+            parent_ticker,
+            tail.chars().next().unwrap_or('F').to_ascii_uppercase()
+        );
+
+        CoinIdentity {
+            ticker,
+            logo: CoinLogo::random(),
+            pitch: format!(
+                "{name} is a hard fork of {parent} that promises to do it right this time."
+            ),
+            name,
+        }
+    }
+}
+
+fn gen_name(archetype: CoinArchetype) -> String {
+    match archetype {
+        // Scam tokens dress up as meme coins
+        CoinArchetype::MemeCoin | CoinArchetype::ScamToken => {
+            format!("{}{}", pick(&MEME_HEADS), pick(&MEME_TAILS))
+        }
+        CoinArchetype::Stablecoin => format!("{}{}", pick(&STABLE_HEADS), pick(&STABLE_TAILS)),
+        CoinArchetype::BlueChip | CoinArchetype::PrivacyCoin => {
+            format!("{}{}{}", pick(&PREFIXES), pick(&SUFFIXES), pick(&TAILS))
+        }
+    }
+}
+
+/// The first letter followed by the next consonants, three or four letters.
+fn gen_ticker(name: &str) -> String {
+    let mut chars = name.chars().filter(|c| c.is_ascii_alphabetic());
+    let mut ticker = String::new();
+
+    if let Some(first) = chars.next() {
+        ticker.push(first);
+    }
+
+    let len = if rand_from_range(0.0..1.0) < 0.5 {
+        3
+    } else {
+        4
+    };
+
+    for c in chars.clone() {
+        if ticker.len() >= len {
+            break;
+        }
+        if !"aeiouAEIOU".contains(c) {
+            ticker.push(c);
+        }
+    }
+
+    for c in chars {
+        if ticker.len() >= len {
+            break;
+        }
+        if "aeiouAEIOU".contains(c) {
+            ticker.push(c);
+        }
+    }
+
+    ticker.to_uppercase()
+}
+
+fn gen_pitch(name: &str) -> String {
+    format!(
+        "{} is {} {} for {}.",
+        name,
+        pick(&PITCH_SUBJECTS),
+        pick(&PITCH_NOUNS),
+        pick(&PITCH_AUDIENCES)
+    )
+}
@@ -4,9 +4,47 @@ use std::collections::VecDeque;
 use std::ops::Range;
 use wasm_bindgen_futures::spawn_local;
 
+use crate::archetype::{ArchetypeProfile, CoinArchetype};
+use crate::chain::{halvings_at, ChainState};
+use crate::coin_identity::{CoinIdentity, CoinLogo};
+use crate::discovery::Discovery;
+use crate::latency::{orphan_rate, stale_rate, StaleStats};
 use crate::market::{GAME_TIME, MAX_SERIES_LENGTH};
 use crate::mining_rig::MINING_RIG;
-use crate::utils::{command_line_output, get_season, rand_from_range, truncate_price};
+use crate::pool::{PoolScheme, PoolState};
+use crate::price_history::{PriceCandle, PriceHistory};
+use crate::rug_risk::{RugRisk, RugSignal};
+use crate::staking::{Stake, StakeTerm};
+use crate::supply::CoinSupply;
+use crate::utils::{
+    command_line_output, get_season, rand_from_range, rand_poisson, truncate_price,
+};
+
+/// Block reward before the archetype's multiplier.
+pub const BASE_BLOCK_REWARD: f64 = 100.0;
+
+/// Stable identity assigned when a coin is created. Names are for display
+/// and chart slots get reused, the id never changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct CoinId(pub u64);
+
+impl CoinId {
+    /// Element id for the coin's row in the coins table.
+    pub fn row_id(&self) -> String {
+        format!("coin-{}-row", self.0)
+    }
+
+    /// Element id for the coin's selection radio.
+    pub fn radio_id(&self) -> String {
+        format!("coin-{}", self.0)
+    }
+}
+
+impl std::fmt::Display for CoinId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CryptoCoin {
@@ -22,7 +60,6 @@ pub struct CryptoCoin {
     pub index: usize,
     pub balance: f64,
     pub shares: f64,
-    pub hashes: f64,
     pub hashes_per_share: f64,
     pub blocks: u64,
     pub shares_per_block: u64,
@@ -31,7 +68,25 @@ pub struct CryptoCoin {
     pub profit_factor: f64,
     pub berth_date: u64,
     pub death_date: Option<u64>,
-    pub share_cooldown: i64,
+    pub archetype: Option<CoinArchetype>,
+    pub beta: Option<f64>,
+    pub rug_risk: Option<RugRisk>,
+    pub id: Option<CoinId>,
+    pub ticker: Option<String>,
+    pub logo: Option<CoinLogo>,
+    pub pitch: Option<String>,
+    pub stake: Option<Stake>,
+    pub chain: Option<ChainState>,
+    pub supply: Option<CoinSupply>,
+    pub history: Option<PriceHistory>,
+    pub profit_history: Option<VecDeque<f64>>,
+    pub forked_from: Option<CoinId>,
+    pub pool: Option<PoolState>,
+    pub discovery: Option<Discovery>,
+    pub stale: Option<StaleStats>,
+    /// Profit factor at the rig's full hash, cached on market updates for
+    /// profit weighted hash allocation.
+    pub profit_weight: Option<f64>,
 }
 
 impl CryptoCoin {
@@ -59,7 +114,6 @@ impl CryptoCoin {
             index,
             balance: 0.0,
             shares: 0.0,
-            hashes: 0.0,
             hashes_per_share,
             blocks: 0,
             shares_per_block,
@@ -68,58 +122,445 @@ impl CryptoCoin {
             profit_factor: 0.0,
             berth_date: day,
             death_date: None,
-            share_cooldown: 0,
+            archetype: None,
+            beta: None,
+            rug_risk: None,
+            id: None,
+            ticker: None,
+            logo: None,
+            pitch: None,
+            stake: None,
+            chain: None,
+            supply: None,
+            history: None,
+            profit_history: None,
+            forked_from: None,
+            pool: None,
+            discovery: None,
+            stale: None,
+            profit_weight: None,
+        }
+    }
+
+    /// A new coin with its volatility, beta, shares per block and reward
+    /// rolled from the archetype's profile.
+    pub fn with_archetype(
+        archetype: CoinArchetype,
+        name: &str,
+        initial_price: f64,
+        index: usize,
+        max_blocks: u64,
+        hashes_per_share: f64,
+        day: u64,
+    ) -> Self {
+        let profile = archetype.profile();
+        let volatility = rand_from_range(profile.volatility.clone());
+
+        let mut coin = CryptoCoin::new(
+            name,
+            initial_price,
+            -volatility..volatility,
+            index,
+            profile.shares_per_block,
+            BASE_BLOCK_REWARD * profile.reward_multiplier,
+            max_blocks,
+            hashes_per_share,
+            day,
+        );
+
+        coin.archetype = Some(archetype);
+        coin.beta = Some(rand_from_range(profile.beta.clone()));
+
+        coin
+    }
+
+    pub fn profile(&self) -> Option<&'static ArchetypeProfile> {
+        self.archetype.map(|a| a.profile())
+    }
+
+    pub fn hint(&self) -> &'static str {
+        match self.archetype {
+            Some(archetype) => archetype.hint(),
+            None => "Unknown",
+        }
+    }
+
+    pub fn set_identity(&mut self, id: CoinId, identity: CoinIdentity) {
+        self.id = Some(id);
+        self.name = identity.name;
+        self.ticker = Some(identity.ticker);
+        self.logo = Some(identity.logo);
+        self.pitch = Some(identity.pitch);
+    }
+
+    /// Saves before coin ids are migrated have none, see
+    /// `Market::migrate_coin_ids`.
+    pub fn id(&self) -> CoinId {
+        self.id.unwrap_or_default()
+    }
+
+    /// Recovers the id of a coin from an older save from its "Coin-N" name.
+    pub fn legacy_id(&self) -> Option<CoinId> {
+        self.name
+            .strip_prefix("Coin-")
+            .and_then(|n| n.parse::<u64>().ok())
+            .map(CoinId)
+    }
+
+    pub fn staked_amount(&self) -> f64 {
+        match &self.stake {
+            Some(stake) => stake.amount,
+            None => 0.0,
+        }
+    }
+
+    /// Stakes coins, topping up the current stake if there is one. A top-up
+    /// must use the running stake's term.
+    pub fn stake_coins(&mut self, amount: f64, term: StakeTerm, day: u64) -> bool {
+        if amount <= 0.0 || amount > self.balance || !self.active {
+            return false;
+        }
+
+        if self.stake.as_ref().is_some_and(|s| s.term != term) {
+            return false;
+        }
+
+        let multiplier = match self.profile() {
+            Some(profile) => profile.reward_multiplier,
+            None => 1.0,
+        };
+
+        self.balance -= amount;
+
+        match &mut self.stake {
+            Some(stake) => stake.amount += amount,
+            None => self.stake = Some(Stake::new(amount, term, multiplier, day)),
+        }
+
+        true
+    }
+
+    /// Returns the stake to the balance, less any early unstake penalty.
+    /// Gives back (returned, penalty).
+    pub fn unstake(&mut self, day: u64) -> Option<(f64, f64)> {
+        let stake = self.stake.take()?;
+        let penalty = stake.early_penalty(day);
+        let returned = stake.amount - penalty;
+
+        self.balance += returned;
+
+        Some((returned, penalty))
+    }
+
+    /// Pays out yield owed into the balance. Gives back the yield and whether
+    /// the stake matured and was returned.
+    pub fn accrue_stake(&mut self, day: u64) -> (f64, bool) {
+        let stake = match &mut self.stake {
+            Some(stake) => stake,
+            None => return (0.0, false),
+        };
+
+        let earned = stake.accrue(day);
+        self.balance += earned;
+
+        if stake.is_mature(day) {
+            self.balance += stake.amount;
+            self.stake = None;
+            return (earned, true);
+        }
+
+        (earned, false)
+    }
+
+    /// Daily or weekly candles over the coin's life, see `PriceHistory`.
+    pub fn candles(&self, days: Option<usize>) -> Vec<PriceCandle> {
+        let history = match &self.history {
+            Some(history) => history,
+            None => return Vec::new(),
+        };
+
+        match days {
+            Some(days) => history.recent_days(days),
+            None => history.lifetime(),
+        }
+    }
+
+    /// Keeps the profit factor at each market update for the last game day.
+    pub fn record_profit_factor(&mut self) {
+        let history = self.profit_history.get_or_insert_with(VecDeque::new);
+        history.push_back(self.profit_factor);
+
+        while history.len() > MAX_SERIES_LENGTH {
+            history.pop_front();
+        }
+    }
+
+    pub fn pool_mut(&mut self) -> &mut PoolState {
+        self.pool
+            .get_or_insert_with(|| PoolState::new(PoolScheme::Solo))
+    }
+
+    pub fn get_pool_scheme(&self) -> PoolScheme {
+        match &self.pool {
+            Some(pool) => pool.scheme,
+            None => PoolScheme::Solo,
+        }
+    }
+
+    /// Switches pools, starting a fresh pool state. Returns the pending PPLNS
+    /// payout left behind in the old pool.
+    pub fn join_pool(&mut self, scheme: PoolScheme) -> f64 {
+        if self.get_pool_scheme() == scheme {
+            return 0.0;
+        }
+
+        let forfeited = self.pool.as_ref().map(|p| p.pending()).unwrap_or(0.0);
+        self.pool = Some(PoolState::new(scheme));
+        forfeited
+    }
+
+    pub fn get_ticker(&self) -> String {
+        match &self.ticker {
+            Some(ticker) => ticker.clone(),
+            None => self.name.to_uppercase(),
+        }
+    }
+
+    pub fn get_pitch(&self) -> &str {
+        match &self.pitch {
+            Some(pitch) => pitch,
+            None => "No whitepaper available.",
+        }
+    }
+
+    pub fn get_beta(&self) -> f64 {
+        self.beta.unwrap_or(1.0)
+    }
+
+    fn amplitude(&self) -> f64 {
+        self.profile().map(|p| p.amplitude).unwrap_or(1.0)
+    }
+
+    pub fn stale_stats(&self) -> StaleStats {
+        self.stale.clone().unwrap_or_default()
+    }
+
+    pub fn stale_stats_mut(&mut self) -> &mut StaleStats {
+        self.stale.get_or_insert_with(StaleStats::default)
+    }
+
+    /// Chance a share is stale at the rig's network latency.
+    pub fn get_stale_rate(&self) -> f64 {
+        let latency = MINING_RIG().get_network_latency_ms();
+        stale_rate(latency)
+    }
+
+    pub fn get_orphan_rate(&self) -> f64 {
+        let latency = MINING_RIG().get_network_latency_ms();
+        orphan_rate(latency)
+    }
+
+    /// Lifetime shares found that were not stale.
+    pub fn accepted_shares(&self) -> f64 {
+        self.discovery().found_shares - self.stale_stats().stale_shares
+    }
+
+    pub fn discovery(&self) -> Discovery {
+        self.discovery.clone().unwrap_or_default()
+    }
+
+    pub fn discovery_mut(&mut self) -> &mut Discovery {
+        self.discovery.get_or_insert_with(Discovery::default)
+    }
+
+    pub fn get_share_eta(&self) -> Option<f64> {
+        self.discovery().share_eta()
+    }
+
+    pub fn get_block_eta(&self) -> Option<f64> {
+        self.discovery().block_eta(self.shares_per_block)
+    }
+
+    /// Time since the last share against the time one is expected to take.
+    pub fn get_share_effort(&self) -> f64 {
+        self.discovery().share_effort()
+    }
+
+    pub fn get_block_effort(&self) -> f64 {
+        self.discovery().block_effort(self.shares_per_block)
+    }
+
+    /// Starts the coin's chain if it has none yet, older saves get one on
+    /// their next market update.
+    pub fn ensure_chain(&mut self, rig_hash: u64) {
+        if self.chain.is_none() {
+            let difficulty = self.get_difficulty();
+            self.chain = Some(ChainState::new(difficulty, rig_hash as f64));
         }
     }
 
-    pub fn get_share_cooldown(&self) -> i64 {
-        self.share_cooldown
+    pub fn set_rival_hash(&mut self, hash_rate: f64) {
+        if let Some(chain) = &mut self.chain {
+            chain.set_rival_hash(hash_rate);
+        }
+    }
+
+    pub fn get_rival_hash(&self) -> f64 {
+        match &self.chain {
+            Some(chain) => chain.rival_hash.unwrap_or(0.0),
+            None => 0.0,
+        }
+    }
+
+    /// Rival miners find blocks on the coin between market updates, using
+    /// up its supply of blocks whether the player mines it or not.
+    pub fn mine_rival_blocks(&mut self) -> u64 {
+        let expected = match &self.chain {
+            Some(chain) => chain.rival_blocks_expected(self.max_blocks),
+            None => return 0,
+        };
+
+        let blocks = rand_poisson(expected).min(self.max_blocks.saturating_sub(self.blocks));
+        if blocks == 0 {
+            return 0;
+        }
+
+        self.blocks += blocks;
+
+        let height = self.blocks;
+        if let Some(chain) = &mut self.chain {
+            chain.retarget(height);
+        }
+
+        blocks
+    }
+
+    pub fn get_player_share(&self) -> f64 {
+        match &self.chain {
+            Some(chain) => chain.player_share(),
+            None => 0.0,
+        }
+    }
+
+    pub fn step_chain(&mut self) {
+        let price_ratio = self.current_price / self.initial_price.max(0.0001);
+
+        if let Some(chain) = &mut self.chain {
+            chain.step(price_ratio);
+        }
+    }
+
+    /// Gives the coin a supply if it has none yet, using its profile's
+    /// premine and turnover.
+    pub fn ensure_supply(&mut self) {
+        if self.supply.is_some() {
+            return;
+        }
+
+        let (premine, turnover) = match self.profile() {
+            Some(profile) => (
+                rand_from_range(profile.premine.clone()),
+                rand_from_range(profile.turnover.clone()),
+            ),
+            None => (0.3, 0.1),
+        };
+
+        self.supply = Some(CoinSupply::new(
+            self.block_reward,
+            self.max_blocks,
+            premine,
+            turnover,
+        ));
     }
 
-    pub fn get_share_cooldown_seconds(&self) -> f64 {
-        self.share_cooldown as f64 / 20.0
+    /// How far through its emission the coin is, from its age or the blocks
+    /// mined, whichever is further along.
+    fn emission_progress(&self) -> f64 {
+        let lifespan = match self.profile() {
+            Some(profile) => profile.lifespan,
+            None => 100.0,
+        };
+
+        let by_age = self.get_age() as f64 / lifespan;
+        let by_blocks = self.blocks as f64 / self.max_blocks.max(1) as f64;
+
+        by_age.max(by_blocks)
     }
 
-    pub fn get_share_cooldown_ticks(&self) -> i64 {
-        let rig_lvl = MINING_RIG().get_level();
+    /// Emits supply and simulates trading for one market update.
+    pub fn step_supply(&mut self, change: f64) {
+        let progress = self.emission_progress();
+        let price = self.current_price;
 
-        let ticks = match rig_lvl {
-            // 1..=5 => 8 * (20 - rig_lvl),
-            // 6..=10 => 7 * (20 - (rig_lvl - 5)),
-            // 11..=15 => 6 * (20 - (rig_lvl - 10)),
-            // 16..=20 => 5 * (20 - (rig_lvl - 15)),
-            // 21..=25 => 4 * (20 - (rig_lvl - 20)),
-            // 26..=30 => 3 * (20 - (rig_lvl - 25)),
-            // 31..=35 => 2 * (20 - (rig_lvl - 30)),
-            // 36..=40 => 1 * (20 - (rig_lvl - 35)),
-            _ => 0,
+        // Liquidity dries up ahead of a rug that shows the signal
+        let liquidity = match &self.rug_risk {
+            Some(rug_risk) if rug_risk.signals.contains(&RugSignal::ShrinkingLiquidity) => {
+                rug_risk.countdown as f64 / rug_risk.lead.max(1) as f64
+            }
+            _ => 1.0,
         };
 
-        ticks as i64
+        if let Some(supply) = &mut self.supply {
+            supply.emit(progress);
+            supply.trade(price, change, liquidity.max(0.1));
+        }
     }
 
-    pub fn set_share_cooldown(&mut self) {
-        let ticks = self.get_share_cooldown_ticks();
+    pub fn add_volume(&mut self, value: f64) {
+        if let Some(supply) = &mut self.supply {
+            supply.add_volume(value);
+        }
+    }
 
-        self.share_cooldown = ticks;
+    pub fn circulating_supply(&self) -> f64 {
+        match &self.supply {
+            Some(supply) => supply.circulating,
+            None => 0.0,
+        }
     }
 
-    pub fn decrement_share_cooldown(&mut self) {
-        if self.share_cooldown > 0 {
-            self.share_cooldown -= 1;
+    pub fn total_supply(&self) -> f64 {
+        match &self.supply {
+            Some(supply) => supply.total,
+            None => 0.0,
         }
     }
 
-    pub fn get_share_progress(&self) -> f64 {
-        self.hashes / self.hashes_per_share
+    pub fn market_cap(&self) -> f64 {
+        match &self.supply {
+            Some(supply) => supply.market_cap(self.current_price),
+            None => 0.0,
+        }
     }
 
-    pub fn get_block_progress(&self) -> f64 {
-        self.shares / self.shares_per_block as f64
+    pub fn volume_24h(&self) -> f64 {
+        match &self.supply {
+            Some(supply) => supply.volume_24h(),
+            None => 0.0,
+        }
     }
 
     pub fn get_difficulty(&self) -> f64 {
-        self.current_price / 800.0
+        match &self.chain {
+            Some(chain) => chain.difficulty,
+            None => self.current_price / 800.0,
+        }
+    }
+
+    pub fn get_network_hash(&self) -> f64 {
+        match &self.chain {
+            Some(chain) => chain.network_hash,
+            None => 0.0,
+        }
+    }
+
+    pub fn get_halvings(&self) -> u64 {
+        halvings_at(self.blocks, self.max_blocks)
+    }
+
+    /// The block reward after halvings, `block_reward` is the launch reward.
+    pub fn get_block_reward(&self) -> f64 {
+        self.block_reward / 2f64.powi(self.get_halvings() as i32)
     }
 
     pub fn get_effective_hash(&self, hash_rate: u64) -> f64 {
@@ -130,28 +571,70 @@ impl CryptoCoin {
 
     fn get_share_reward(&self, hash_rate: u64) -> f64 {
         let effective_hash = self.get_effective_hash(hash_rate);
-        (self.block_reward / self.shares_per_block as f64)
+        (self.get_block_reward() / self.shares_per_block as f64)
             * (1.0 + (effective_hash as f64 / 10000.0))
     }
 
     pub fn calculate_rug_chance(&self) -> f64 {
         let age = self.get_age();
-        let rug_chance = 0.01 * (age as f64 / 100.0).powf(2.0);
+
+        let (lifespan, rug_multiplier) = match self.profile() {
+            Some(profile) => (profile.lifespan, profile.rug_multiplier),
+            None => (100.0, 1.0),
+        };
+
+        let cap_factor = match &self.supply {
+            Some(supply) => supply.rug_factor(self.current_price),
+            None => 1.0,
+        };
+
+        let rug_chance = 0.01 * (age as f64 / lifespan).powf(2.0) * rug_multiplier * cap_factor;
         rug_chance
     }
 
+    /// Rolls the rug chance and, on a hit, schedules the rug pull for a few
+    /// days out instead of pulling it straight away.
+    pub fn roll_rug_risk(&mut self) -> bool {
+        if self.rug_risk.is_some() || self.death_date.is_some() {
+            return false;
+        }
+
+        if rand_from_range(0.0..1.0) < self.calculate_rug_chance() {
+            self.rug_risk = Some(RugRisk::schedule());
+            return true;
+        }
+
+        false
+    }
+
+    pub fn tick_rug_risk(&mut self) -> bool {
+        match &mut self.rug_risk {
+            Some(rug_risk) => rug_risk.tick(),
+            None => false,
+        }
+    }
+
+    pub fn rug_signals(&self, due_diligence: u64) -> Vec<RugSignal> {
+        match &self.rug_risk {
+            Some(rug_risk) => rug_risk.visible_signals(due_diligence),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn rug_days_left(&self) -> Option<f64> {
+        self.rug_risk.as_ref().map(|r| r.days_left())
+    }
+
     fn calculate_shares_per_minute(&self, hash_rate: u64) -> f64 {
         let effective_hash: f64 = self.get_effective_hash(hash_rate);
         let hashes_per_call: f64 = effective_hash / 4.0;
-        let cooldown_ticks: i64 = self.get_share_cooldown_ticks();
-        let cooldown_seconds: f64 = cooldown_ticks as f64 / 20.0;
         let calls_per_share: f64 = self.hashes_per_share / hashes_per_call;
 
         let seconds_per_share: f64 = calls_per_share / 20.0;
 
-        let minutes_per_share: f64 = (seconds_per_share + cooldown_seconds) / 60.0;
+        let minutes_per_share: f64 = seconds_per_share / 60.0;
         let shares_per_minute: f64 = 1.0 / minutes_per_share;
-        shares_per_minute
+        shares_per_minute * (1.0 - self.get_stale_rate())
     }
 
     fn calculate_power_cost_per_minute(&self, day: u64) -> f64 {
@@ -195,34 +678,111 @@ impl CryptoCoin {
         }
     }
 
-    pub fn calculate_profit_factor(&mut self, hash_rate: u64) -> f64 {
+    pub fn get_profit_weight(&self, hash_rate: u64) -> f64 {
+        self.profit_weight
+            .unwrap_or_else(|| self.calculate_profit_factor(hash_rate))
+    }
+
+    pub fn calculate_profit_factor(&self, hash_rate: u64) -> f64 {
         let spm = self.calculate_shares_per_minute(hash_rate);
         let coins_share = self.get_share_reward(hash_rate);
-        (spm * coins_share) * self.current_price
+        let scheme = self.get_pool_scheme();
+
+        // Solo is only paid for blocks that beat the rivals and propagate
+        let block_odds = match (scheme, &self.chain) {
+            (PoolScheme::Solo, Some(chain)) => {
+                (1.0 - chain.rival_share()) * (1.0 - self.get_orphan_rate())
+            }
+            (PoolScheme::Solo, None) => 1.0 - self.get_orphan_rate(),
+            _ => 1.0,
+        };
+
+        (spm * coins_share) * block_odds * self.current_price * (1.0 - scheme.fee())
     }
 
-    pub fn hash_coin(&mut self, hash_rate: u64) {
-        let share_cooldown = self.get_share_cooldown() != 0;
+    /// Runs one tick of hashing and returns the coins earned from shares and
+    /// from block bonuses.
+    pub fn hash_coin(&mut self, hash_rate: u64) -> (f64, f64) {
+        let mut share_coins = 0.0;
+        let mut bonus_coins = 0.0;
 
-        if self.blocks >= self.max_blocks || share_cooldown || !self.active {
-            return;
+        if self.blocks >= self.max_blocks || !self.active {
+            return (share_coins, bonus_coins);
+        }
+
+        if let Some(chain) = &mut self.chain {
+            chain.record_player_hash(hash_rate as f64);
         }
 
         let effective_hash = self.get_effective_hash(hash_rate);
-        self.hashes += effective_hash / 6.0;
 
-        let new_shares = (self.hashes / self.hashes_per_share).floor();
+        // Every hash has the same small chance to solve a share and every
+        // share the same chance to solve a block, so both are Poisson draws
+        let share_rate = effective_hash / 6.0 / self.hashes_per_share;
+        let found_shares = rand_poisson(share_rate) as f64;
+
+        let previous_accepted = self.accepted_shares();
+
+        // Shares and blocks still in flight when the network moves on are lost
+        let latency = MINING_RIG().get_network_latency_ms();
+        let stale_shares =
+            (rand_poisson(found_shares * stale_rate(latency)) as f64).min(found_shares);
+        let new_shares = found_shares - stale_shares;
+
+        // Rival miners win some of the blocks the player's shares would solve
+        let block_odds = match &self.chain {
+            Some(chain) => 1.0 - chain.rival_share(),
+            None => 1.0,
+        };
+        let found_blocks =
+            rand_poisson(new_shares / self.shares_per_block.max(1) as f64 * block_odds);
+        let orphaned_blocks =
+            rand_poisson(found_blocks as f64 * orphan_rate(latency)).min(found_blocks);
+
+        if stale_shares > 0.0 || orphaned_blocks > 0 {
+            let stale = self.stale_stats_mut();
+            stale.stale_shares += stale_shares;
+            stale.orphaned_blocks += orphaned_blocks;
+        }
+
+        if orphaned_blocks > 0 {
+            let msg = format!(
+                "Block orphaned on {}, it reached the network too late",
+                self.name
+            );
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+
+        // A lucky draw can't mine past the supply cap
+        let found_blocks =
+            (found_blocks - orphaned_blocks).min(self.max_blocks.saturating_sub(self.blocks));
+
+        let scheme = self.get_pool_scheme();
+
         if new_shares >= 1.0 {
-            self.hashes -= self.hashes_per_share * new_shares;
             self.shares += new_shares;
 
             let share_divisor = self.hash_divisor(hash_rate);
-            self.balance += self.get_share_reward(hash_rate) * new_shares;
+            let share_value = self.get_share_reward(hash_rate);
+            let shares_per_block = self.shares_per_block;
+            let payout =
+                self.pool_mut()
+                    .submit(new_shares, found_blocks, share_value, shares_per_block);
+
+            share_coins = payout.paid;
+            self.balance += share_coins;
 
-            if share_divisor < 1000.0 && self.shares % share_divisor == 0.0 {
+            // Shares arrive in batches, so announce each multiple crossed
+            let total_shares = previous_accepted + new_shares;
+            let crossed = (total_shares / share_divisor).floor()
+                > (previous_accepted / share_divisor).floor();
+
+            if share_divisor < 1000.0 && crossed {
                 let msg = format!(
                     "{} shares accepted for {}, yay!",
-                    self.shares as u64, self.name
+                    total_shares as u64, self.name
                 );
                 spawn_local(async move {
                     command_line_output(&msg).await;
@@ -230,15 +790,40 @@ impl CryptoCoin {
             }
         }
 
-        let mut new_blocks = (self.shares / self.shares_per_block as f64).floor();
-        let max_new_blocks = (self.max_blocks - self.blocks) as f64;
+        let shares_per_block = self.shares_per_block;
+        self.discovery_mut()
+            .record(share_rate, found_shares, found_blocks, shares_per_block);
 
-        new_blocks = new_blocks.min(max_new_blocks).floor();
+        let new_blocks = found_blocks as f64;
 
         if new_blocks >= 1.0 {
-            self.shares -= self.shares_per_block as f64 * new_blocks;
+            let halvings = self.get_halvings();
+
+            // Bonus is paid at the reward of the blocks just found, pools keep it
+            if scheme == PoolScheme::Solo {
+                bonus_coins = self.get_block_reward() * 0.25 * new_blocks;
+            }
+
+            // Shares only count towards the block they were found in
+            self.shares = 0.0;
             self.blocks += new_blocks as u64;
 
+            if self.get_halvings() > halvings {
+                let msg = format!(
+                    "{} block reward halved to {}",
+                    self.name,
+                    truncate_price(self.get_block_reward())
+                );
+                spawn_local(async move {
+                    command_line_output(&msg).await;
+                });
+            }
+
+            let blocks = self.blocks;
+            if let Some(chain) = &mut self.chain {
+                chain.retarget(blocks);
+            }
+
             if self.blocks % self.hash_divisor_blocks(hash_rate) == 0 {
                 let msg = format!("Block {} mined for {}, yay!", self.blocks, self.name);
                 spawn_local(async move {
@@ -246,9 +831,11 @@ impl CryptoCoin {
                 });
             }
 
-            // 25% bonus for completing a block
-            self.balance += self.block_reward * 0.25 * new_blocks;
+            // 25% bonus for completing a block solo
+            self.balance += bonus_coins;
         }
+
+        (share_coins, bonus_coins)
     }
 
     pub fn get_age(&self) -> u64 {
@@ -258,7 +845,7 @@ impl CryptoCoin {
         GAME_TIME().day - self.berth_date
     }
 
-    pub fn update_price(&mut self) {
+    pub fn update_price(&mut self, market_return: f64, news_impact: f64, npc_impact: f64) {
         let starting_price = self.current_price;
 
         // Encourage a trend correction if the trend is too strong
@@ -271,9 +858,11 @@ impl CryptoCoin {
         };
         self.trend += trend_adjustment;
 
-        // Market sentiment factor
+        let amplitude = self.amplitude();
+
+        // Idiosyncratic sentiment factor
         let sentiment_factor = -0.02..0.02;
-        let sentiment = rand_from_range(sentiment_factor);
+        let sentiment = rand_from_range(sentiment_factor) * amplitude;
         self.trend += sentiment;
 
         // Periodic sawtooth pattern
@@ -281,28 +870,43 @@ impl CryptoCoin {
         let position = (self.prices.len() % period) as f64;
         let sawtooth = (position / period as f64) - 0.5; // Range from -0.5 to 0.5
 
-        // Combine sawtooth with random change and trend
-        let change_percent =
-            sawtooth * 0.05 + rand_from_range(self.volatility.clone()) + self.trend;
+        // Archetype drift, stablecoins are pulled back towards their peg
+        let (trend_bias, mean_reversion) = match self.profile() {
+            Some(profile) => (profile.trend_bias, profile.mean_reversion),
+            None => (0.0, 0.0),
+        };
 
-        // Random events with variable impact
-        if rand_from_range(0.0..1.0) < 0.01 {
-            let event = rand_from_range(-0.1..0.1);
-            self.current_price *= 1.0 + event;
-        } else {
-            self.current_price *= 1.0 + change_percent;
+        if mean_reversion > 0.0 {
+            self.trend *= 1.0 - mean_reversion;
         }
 
+        let reversion = if self.current_price > 0.0 {
+            mean_reversion * (self.initial_price / self.current_price - 1.0)
+        } else {
+            0.0
+        };
+
+        // Combine sawtooth with random change and trend
+        let change_percent = sawtooth * 0.05 * amplitude
+            + rand_from_range(self.volatility.clone())
+            + self.trend
+            + trend_bias
+            + reversion
+            + self.get_beta() * market_return;
+
+        self.current_price *= 1.0 + change_percent;
+
         // Seasonality effect
-        let seasonality = 0.01 * (self.prices.len() as f64 / 10.0).sin()
-            + 0.005 * (self.prices.len() as f64 / 50.0).cos();
+        let seasonality = (0.01 * (self.prices.len() as f64 / 10.0).sin()
+            + 0.005 * (self.prices.len() as f64 / 50.0).cos())
+            * amplitude;
         self.current_price *= 1.0 + seasonality;
 
-        // Introduce news impact
-        if rand_from_range(0.0..1.0) < 0.015 {
-            let news_impact = rand_from_range(-0.05..0.05);
-            self.current_price *= 1.0 + news_impact;
-        }
+        // Headlines from the news feed
+        self.current_price *= 1.0 + news_impact;
+
+        // Buying and selling from NPC traders
+        self.current_price *= 1.0 + npc_impact;
 
         // Clamp price to prevent excessive growth or decline
         if self.current_price > 100_000.0 {
@@ -328,6 +932,13 @@ impl CryptoCoin {
             self.prices.remove(0);
         }
 
+        if self.active {
+            let day = GAME_TIME().day;
+            self.history
+                .get_or_insert_with(PriceHistory::new)
+                .record(day, self.current_price);
+        }
+
         self.trend_direction
             .push_front(self.current_price > starting_price);
 
@@ -0,0 +1,168 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+/// Game loop ticks in one real second.
+pub const TICKS_PER_SECOND: f64 = 20.0;
+
+/// Tracks how shares and blocks were actually found against the rate they
+/// were expected at, for luck, streaks and time estimates.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct Discovery {
+    /// Expected shares per tick at the last hash rate.
+    pub share_rate: f64,
+    pub ticks_since_share: u64,
+    pub ticks_since_block: u64,
+    pub expected_shares: f64,
+    pub found_shares: f64,
+    pub expected_blocks: f64,
+    pub found_blocks: u64,
+    pub fastest_block: Option<u64>,
+    pub longest_drought: u64,
+    pub lucky_streak: u64,
+    pub best_lucky_streak: u64,
+}
+
+impl Discovery {
+    /// Records one tick of mining where `share_rate` shares were expected.
+    pub fn record(&mut self, share_rate: f64, shares: f64, blocks: u64, shares_per_block: u64) {
+        let per_block = shares_per_block.max(1) as f64;
+
+        self.share_rate = share_rate;
+        self.expected_shares += share_rate;
+        self.expected_blocks += share_rate / per_block;
+        self.found_shares += shares;
+        self.ticks_since_share += 1;
+        self.ticks_since_block += 1;
+
+        if shares > 0.0 {
+            self.ticks_since_share = 0;
+        }
+
+        if blocks == 0 {
+            self.longest_drought = self.longest_drought.max(self.ticks_since_block);
+            return;
+        }
+
+        // A block found in less than the expected time extends the streak
+        if self.block_effort(shares_per_block) < 1.0 {
+            self.lucky_streak += 1;
+            self.best_lucky_streak = self.best_lucky_streak.max(self.lucky_streak);
+        } else {
+            self.lucky_streak = 0;
+        }
+
+        let ticks = self.ticks_since_block;
+        self.fastest_block = Some(self.fastest_block.map_or(ticks, |f| f.min(ticks)));
+        self.found_blocks += blocks;
+        self.ticks_since_block = 0;
+    }
+
+    pub fn share_luck(&self) -> f64 {
+        if self.expected_shares <= 0.0 {
+            return 1.0;
+        }
+        self.found_shares / self.expected_shares
+    }
+
+    pub fn block_luck(&self) -> f64 {
+        if self.expected_blocks <= 0.0 {
+            return 1.0;
+        }
+        self.found_blocks as f64 / self.expected_blocks
+    }
+
+    /// Expected seconds until the next share. Discovery has no memory so
+    /// this does not shrink while waiting.
+    pub fn share_eta(&self) -> Option<f64> {
+        if self.share_rate <= 0.0 {
+            return None;
+        }
+        Some(1.0 / self.share_rate / TICKS_PER_SECOND)
+    }
+
+    pub fn block_eta(&self, shares_per_block: u64) -> Option<f64> {
+        self.share_eta()
+            .map(|eta| eta * shares_per_block.max(1) as f64)
+    }
+
+    /// Time spent since the last share as a fraction of the expected time.
+    pub fn share_effort(&self) -> f64 {
+        self.ticks_since_share as f64 * self.share_rate
+    }
+
+    pub fn block_effort(&self, shares_per_block: u64) -> f64 {
+        self.ticks_since_block as f64 * self.share_rate / shares_per_block.max(1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f64 = 0.5;
+    const SHARES_PER_BLOCK: u64 = 10;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn wait(discovery: &mut Discovery, ticks: u64) {
+        for _ in 0..ticks {
+            discovery.record(RATE, 0.0, 0, SHARES_PER_BLOCK);
+        }
+    }
+
+    #[test]
+    fn starts_even_with_no_estimates() {
+        let discovery = Discovery::default();
+
+        assert_eq!(discovery.share_luck(), 1.0);
+        assert_eq!(discovery.block_luck(), 1.0);
+        assert_eq!(discovery.share_eta(), None);
+        assert_eq!(discovery.block_eta(SHARES_PER_BLOCK), None);
+    }
+
+    #[test]
+    fn estimates_follow_the_share_rate() {
+        let mut discovery = Discovery::default();
+        wait(&mut discovery, 4);
+
+        assert!(close(discovery.share_eta().unwrap(), 0.1));
+        assert!(close(discovery.block_eta(SHARES_PER_BLOCK).unwrap(), 1.0));
+        assert!(close(discovery.share_effort(), 2.0));
+        assert!(close(discovery.block_effort(SHARES_PER_BLOCK), 0.2));
+        assert_eq!(discovery.longest_drought, 4);
+    }
+
+    #[test]
+    fn shares_reset_the_share_wait_only() {
+        let mut discovery = Discovery::default();
+        wait(&mut discovery, 3);
+        discovery.record(RATE, 2.0, 0, SHARES_PER_BLOCK);
+
+        assert_eq!(discovery.ticks_since_share, 0);
+        assert_eq!(discovery.ticks_since_block, 4);
+        assert!(close(discovery.share_luck(), 1.0));
+    }
+
+    #[test]
+    fn quick_blocks_build_a_streak_and_slow_ones_end_it() {
+        let mut discovery = Discovery::default();
+
+        wait(&mut discovery, 5);
+        discovery.record(RATE, 1.0, 1, SHARES_PER_BLOCK);
+        assert_eq!(discovery.lucky_streak, 1);
+        assert_eq!(discovery.fastest_block, Some(6));
+        assert_eq!(discovery.ticks_since_block, 0);
+
+        wait(&mut discovery, 30);
+        discovery.record(RATE, 1.0, 1, SHARES_PER_BLOCK);
+        assert_eq!(discovery.lucky_streak, 0);
+        assert_eq!(discovery.best_lucky_streak, 1);
+        assert_eq!(discovery.fastest_block, Some(6));
+        assert_eq!(discovery.longest_drought, 30);
+
+        assert_eq!(discovery.found_blocks, 2);
+        assert!(close(discovery.block_luck(), 2.0 / (37.0 * RATE / 10.0)));
+    }
+}
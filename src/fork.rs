@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+
+use crate::coin_identity::CoinIdentity;
+use crate::crypto_coin::{CoinId, CryptoCoin};
+use crate::utils::rand_from_range;
+
+/// Forks add chart slots past the starting ten up to this many coins.
+pub const MAX_MARKET_SLOTS: usize = 12;
+/// Coins younger than this many days are too new to fork.
+pub const FORK_MIN_AGE: u64 = 5;
+/// Chance per market update that an eligible coin forks.
+pub const FORK_CHANCE: f64 = 0.0003;
+/// The fork launches at this share of the parent's price.
+pub const FORK_PRICE_SPLIT: std::ops::Range<f64> = 0.1..0.5;
+
+/// Whether the coin is established enough to split. Forks never fork again
+/// and a coin only has one live fork at a time.
+pub fn can_fork(coin: &CryptoCoin, coins: &[CryptoCoin]) -> bool {
+    coin.active
+        && coin.forked_from.is_none()
+        && coin.get_age() >= FORK_MIN_AGE
+        && coin.blocks < coin.max_blocks
+        && !coins
+            .iter()
+            .any(|c| c.active && c.forked_from == Some(coin.id()))
+}
+
+/// Splits the parent into a new coin on its own chain. The fork keeps the
+/// parent's archetype, volatility and reward but gets a fresh price path,
+/// share difficulty and the parent's remaining blocks. Balances are credited
+/// by the caller.
+pub fn split(
+    parent: &CryptoCoin,
+    id: CoinId,
+    index: usize,
+    day: u64,
+    taken: &[String],
+    rig_hash: u64,
+) -> CryptoCoin {
+    let identity = CoinIdentity::fork(&parent.name, &parent.get_ticker(), taken);
+    let price = parent.current_price * rand_from_range(FORK_PRICE_SPLIT);
+    let max_blocks = parent.max_blocks.saturating_sub(parent.blocks).max(10);
+    let hashes_per_share = parent.hashes_per_share * rand_from_range(0.5..1.5);
+
+    let mut coin = CryptoCoin::new(
+        &identity.name,
+        price,
+        parent.volatility.clone(),
+        index,
+        parent.shares_per_block,
+        parent.block_reward,
+        max_blocks,
+        hashes_per_share,
+        day,
+    );
+
+    // The parent's reward and volatility already carry its archetype, so
+    // copy it over rather than rolling the profile again
+    coin.archetype = parent.archetype;
+    coin.beta = parent.beta;
+    coin.set_identity(id, identity);
+    coin.forked_from = Some(parent.id());
+    coin.ensure_chain(rig_hash);
+    coin.ensure_supply();
+
+    coin
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use wasm_bindgen::JsValue;
 use web_sys::DomException;
 
+use crate::allocation::{split_hash, HashAllocation};
+use crate::crypto_coin::{CoinId, CryptoCoin};
 use crate::galaxy_api::GalaxyResponse;
 use crate::market::Market;
 use crate::mining_rig::MiningRig;
@@ -124,12 +126,22 @@ pub struct SelectionMulti {
     pub name: String,
     pub index: usize,
     pub selection_index: usize,
+    pub id: Option<CoinId>,
+    /// Share of hash in percent when the allocation is custom.
+    pub weight: Option<f64>,
+}
+
+impl SelectionMulti {
+    pub fn coin_id(&self) -> CoinId {
+        self.id.unwrap_or_default()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Default, Debug)]
 pub struct SelectionMultiList {
     pub selections: Vec<SelectionMulti>,
     pub max_selectable: u8,
+    pub allocation: Option<HashAllocation>,
 }
 
 impl SelectionMultiList {
@@ -137,6 +149,7 @@ impl SelectionMultiList {
         SelectionMultiList {
             selections: Vec::new(),
             max_selectable: 1,
+            allocation: None,
         }
     }
 
@@ -156,13 +169,13 @@ impl SelectionMultiList {
         self.selections.len() as u8 >= self.max_selectable
     }
 
-    pub fn is_selected(&self, index: usize) -> bool {
-        self.selections.iter().any(|s| s.index == index)
+    pub fn is_selected(&self, id: CoinId) -> bool {
+        self.selections.iter().any(|s| s.coin_id() == id)
     }
 
-    pub fn make_selection(&mut self, index: usize, name: &str, do_toggle: bool) {
-        if self.is_selected(index) && do_toggle {
-            self.unmake_selection(index);
+    pub fn make_selection(&mut self, coin: &CryptoCoin, do_toggle: bool) {
+        if self.is_selected(coin.id()) && do_toggle {
+            self.unmake_selection(coin.id());
         } else {
             if self.is_full() {
                 self.remove(0);
@@ -178,9 +191,11 @@ impl SelectionMultiList {
             }
 
             let selection = SelectionMulti {
-                index,
-                name: name.to_string(),
+                index: coin.index,
+                name: coin.name.clone(),
                 selection_index,
+                id: Some(coin.id()),
+                weight: None,
             };
             self.insert(selection);
         }
@@ -188,12 +203,37 @@ impl SelectionMultiList {
         self.update_ui();
     }
 
-    pub fn unmake_selection(&mut self, index: usize) {
-        if let Some(position) = self.selections.iter().position(|s| s.index == index) {
+    pub fn unmake_selection(&mut self, id: CoinId) {
+        if let Some(position) = self.selections.iter().position(|s| s.coin_id() == id) {
             self.remove(position);
         }
     }
 
+    /// Drops every selection that isn't one of `ids`, keeping the rest with
+    /// their slots and weights.
+    pub fn retain_coins(&mut self, ids: &[CoinId]) {
+        self.selections.retain(|s| ids.contains(&s.coin_id()));
+    }
+
+    /// Fills in coin ids for selections from saves that only stored names,
+    /// dropping any that no longer match a coin.
+    pub fn migrate_coin_ids(&mut self, coins: &[CryptoCoin]) {
+        self.selections.retain_mut(|s| {
+            if s.id.is_some() {
+                return true;
+            }
+
+            match coins.iter().find(|c| c.name == s.name) {
+                Some(coin) => {
+                    s.id = Some(coin.id());
+                    s.index = coin.index;
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+
     pub fn update_ui(&self) {
         let window = web_sys::window().expect("should have a window");
         let document = window.document().expect("should have a document");
@@ -218,7 +258,8 @@ impl SelectionMultiList {
         }
 
         for selection in &self.selections {
-            let coin_name = &selection.name;
+            let radio_id = selection.coin_id().radio_id();
+            let row_id = selection.coin_id().row_id();
 
             for i in 0..radios.length() {
                 let radio = radios.get(i).expect("should have radio");
@@ -226,7 +267,7 @@ impl SelectionMultiList {
                     .dyn_into::<web_sys::HtmlInputElement>()
                     .expect("should be a radio");
 
-                if radio.id() == *coin_name {
+                if radio.id() == radio_id {
                     radio.set_checked(true);
                 }
             }
@@ -235,7 +276,7 @@ impl SelectionMultiList {
                 let row = rows.get(i).expect("should have row");
                 let row = row.dyn_into::<web_sys::Element>().expect("should be a row");
 
-                if row.id() == format!("{}-row", coin_name) {
+                if row.id() == row_id {
                     row.set_class_name(&format!("selected-{}", selection.index));
                 }
             }
@@ -260,6 +301,59 @@ impl SelectionMultiList {
         self.selections.iter().find(|s| s.selection_index == index)
     }
 
+    pub fn get_allocation(&self) -> HashAllocation {
+        self.allocation.unwrap_or(HashAllocation::Equal)
+    }
+
+    pub fn set_allocation(&mut self, allocation: HashAllocation) {
+        self.allocation = Some(allocation);
+    }
+
+    pub fn set_weight(&mut self, id: CoinId, weight: f64) {
+        if let Some(selection) = self.selections.iter_mut().find(|s| s.coin_id() == id) {
+            selection.weight = Some(weight.clamp(0.0, 100.0));
+        }
+    }
+
+    /// The custom weight for a selection, an even share until one is set.
+    /// Weights are relative, `split_hash` scales them to the whole rig.
+    pub fn weight_of(&self, selection: &SelectionMulti) -> f64 {
+        selection
+            .weight
+            .unwrap_or(100.0 / self.selections.len().max(1) as f64)
+    }
+
+    /// Hash rate for each selected coin that can still be mined, split by the
+    /// chosen allocation.
+    pub fn hash_allocation(&self, coins: &[CryptoCoin], hash_rate: u64) -> Vec<(CoinId, u64)> {
+        let mineable: Vec<(&SelectionMulti, &CryptoCoin)> = self
+            .selections
+            .iter()
+            .filter_map(|s| {
+                coins
+                    .iter()
+                    .find(|c| c.id() == s.coin_id())
+                    .filter(|c| c.active && c.blocks < c.max_blocks)
+                    .map(|c| (s, c))
+            })
+            .collect();
+
+        let weights: Vec<f64> = mineable
+            .iter()
+            .map(|(selection, coin)| match self.get_allocation() {
+                HashAllocation::Equal => 1.0,
+                HashAllocation::Custom => self.weight_of(selection),
+                HashAllocation::Profit => coin.get_profit_weight(hash_rate),
+            })
+            .collect();
+
+        mineable
+            .iter()
+            .map(|(selection, _)| selection.coin_id())
+            .zip(split_hash(hash_rate, &weights))
+            .collect()
+    }
+
     pub fn get_upgrade_cost(&self) -> f64 {
         match self.max_selectable {
             1 => 10_000.0,
@@ -0,0 +1,188 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::archetype::CoinArchetype;
+use crate::crypto_coin::{CoinId, CryptoCoin};
+use crate::rug_risk::RugRisk;
+use crate::utils::rand_from_range;
+
+/// Number of upcoming coins kept announced at any time.
+pub const ICO_PIPELINE_SIZE: usize = 3;
+/// Days between an ICO being announced and the earliest day it can list.
+pub const ICO_LEAD_DAYS: Range<f64> = 2.0..5.0;
+/// Days past the listing day before a slot is forced open for the coin.
+pub const ICO_MAX_DELAY_DAYS: u64 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum IcoOutcome {
+    Moon,
+    Steady,
+    Flop,
+    Rug,
+}
+
+impl IcoOutcome {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IcoOutcome::Moon => "mooned",
+            IcoOutcome::Steady => "listed steady",
+            IcoOutcome::Flop => "flopped",
+            IcoOutcome::Rug => "rugged at launch",
+        }
+    }
+
+    /// Listing price as a multiple of the presale price.
+    pub fn price_multiplier(&self) -> Range<f64> {
+        match self {
+            IcoOutcome::Moon => 2.0..5.0,
+            IcoOutcome::Steady => 0.9..1.5,
+            IcoOutcome::Flop => 0.3..0.8,
+            IcoOutcome::Rug => 0.8..1.2,
+        }
+    }
+
+    /// Chances of (moon, steady, flop, rug) for each archetype.
+    fn weights(archetype: CoinArchetype) -> [f64; 4] {
+        match archetype {
+            CoinArchetype::Stablecoin => [0.0, 0.9, 0.1, 0.0],
+            CoinArchetype::BlueChip => [0.3, 0.5, 0.2, 0.0],
+            CoinArchetype::MemeCoin => [0.4, 0.1, 0.45, 0.05],
+            CoinArchetype::PrivacyCoin => [0.2, 0.5, 0.3, 0.0],
+            CoinArchetype::ScamToken => [0.15, 0.0, 0.25, 0.6],
+        }
+    }
+
+    pub fn roll(archetype: CoinArchetype) -> Self {
+        let outcomes = [
+            IcoOutcome::Moon,
+            IcoOutcome::Steady,
+            IcoOutcome::Flop,
+            IcoOutcome::Rug,
+        ];
+        let weights = IcoOutcome::weights(archetype);
+        let mut roll = rand_from_range(0.0..weights.iter().sum());
+
+        for (outcome, weight) in outcomes.iter().zip(weights.iter()) {
+            if roll < *weight {
+                return *outcome;
+            }
+            roll -= weight;
+        }
+
+        IcoOutcome::Flop
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IcoOffering {
+    pub coin: CryptoCoin,
+    pub presale_price: f64,
+    pub allocation_cap: f64,
+    pub purchased: f64,
+    pub announced_day: u64,
+    pub listing_day: u64,
+}
+
+impl IcoOffering {
+    /// The coin's generated price becomes the presale price, the cap is a
+    /// share of what the coin will pay out in block rewards.
+    pub fn new(coin: CryptoCoin, day: u64) -> Self {
+        let allocation_cap =
+            (coin.max_blocks as f64 * coin.block_reward * rand_from_range(0.1..0.3)).floor();
+
+        IcoOffering {
+            presale_price: coin.current_price,
+            allocation_cap: allocation_cap.max(10.0),
+            purchased: 0.0,
+            announced_day: day,
+            listing_day: day + rand_from_range(ICO_LEAD_DAYS) as u64,
+            coin,
+        }
+    }
+
+    pub fn id(&self) -> CoinId {
+        self.coin.id()
+    }
+
+    pub fn remaining_allocation(&self) -> f64 {
+        (self.allocation_cap - self.purchased).max(0.0)
+    }
+
+    pub fn is_ready(&self, day: u64) -> bool {
+        day >= self.listing_day
+    }
+
+    pub fn is_overdue(&self, day: u64) -> bool {
+        day >= self.listing_day + ICO_MAX_DELAY_DAYS
+    }
+
+    /// Lists the coin in the given chart slot. The outcome follows the coin's
+    /// hidden archetype, a rug at launch pulls on the next market update.
+    pub fn list(mut self, index: usize, day: u64) -> (CryptoCoin, IcoOutcome) {
+        let outcome = match self.coin.archetype {
+            Some(archetype) => IcoOutcome::roll(archetype),
+            None => IcoOutcome::Steady,
+        };
+
+        let price = self.presale_price * rand_from_range(outcome.price_multiplier());
+
+        self.coin.index = index;
+        self.coin.berth_date = day;
+        self.coin.initial_price = price;
+        self.coin.current_price = price;
+        self.coin.prices = vec![price];
+
+        if outcome == IcoOutcome::Rug {
+            let mut rug_risk = RugRisk::schedule();
+            rug_risk.countdown = 1;
+            self.coin.rug_risk = Some(rug_risk);
+        }
+
+        (self.coin, outcome)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct IcoPipeline {
+    pub offerings: Vec<IcoOffering>,
+}
+
+impl IcoPipeline {
+    pub fn new() -> Self {
+        IcoPipeline {
+            offerings: Vec::new(),
+        }
+    }
+
+    pub fn needs_offering(&self) -> bool {
+        self.offerings.len() < ICO_PIPELINE_SIZE
+    }
+
+    pub fn announce(&mut self, offering: IcoOffering) {
+        self.offerings.push(offering);
+        self.offerings.sort_by_key(|o| o.listing_day);
+    }
+
+    pub fn offering(&self, id: CoinId) -> Option<&IcoOffering> {
+        self.offerings.iter().find(|o| o.id() == id)
+    }
+
+    pub fn offering_mut(&mut self, id: CoinId) -> Option<&mut IcoOffering> {
+        self.offerings.iter_mut().find(|o| o.id() == id)
+    }
+
+    /// Removes the first offering whose listing day has come.
+    pub fn take_ready(&mut self, day: u64) -> Option<IcoOffering> {
+        let index = self.offerings.iter().position(|o| o.is_ready(day))?;
+        Some(self.offerings.remove(index))
+    }
+
+    pub fn has_overdue(&self, day: u64) -> bool {
+        self.offerings.iter().any(|o| o.is_overdue(day))
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.offerings.iter().map(|o| o.coin.name.clone()).collect()
+    }
+}
@@ -0,0 +1,173 @@
+#![allow(dead_code)]
+
+pub const SMA_PERIOD: usize = 12;
+pub const EMA_PERIOD: usize = 12;
+pub const BOLLINGER_PERIOD: usize = 20;
+pub const BOLLINGER_WIDTH: f64 = 2.0;
+pub const RSI_PERIOD: usize = 14;
+
+/// Which overlays are switched on in the chart.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct IndicatorSet {
+    pub sma: bool,
+    pub ema: bool,
+    pub bollinger: bool,
+    pub rsi: bool,
+    pub volume: bool,
+}
+
+impl IndicatorSet {
+    pub fn any(&self) -> bool {
+        self.sma || self.ema || self.bollinger || self.rsi || self.volume
+    }
+}
+
+// Every indicator returns one value per price so it lines up with the chart.
+// Until a full period is available the window covers what there is.
+
+fn window(prices: &[f64], index: usize, period: usize) -> &[f64] {
+    let start = (index + 1).saturating_sub(period);
+    &prices[start..=index]
+}
+
+pub fn sma(prices: &[f64], period: usize) -> Vec<f64> {
+    (0..prices.len())
+        .map(|i| {
+            let window = window(prices, i, period);
+            window.iter().sum::<f64>() / window.len() as f64
+        })
+        .collect()
+}
+
+pub fn ema(prices: &[f64], period: usize) -> Vec<f64> {
+    let alpha = 2.0 / (period as f64 + 1.0);
+    let mut values = Vec::with_capacity(prices.len());
+
+    for (i, price) in prices.iter().enumerate() {
+        let value = match i {
+            0 => *price,
+            _ => alpha * price + (1.0 - alpha) * values[i - 1],
+        };
+        values.push(value);
+    }
+
+    values
+}
+
+/// Upper and lower bands, `width` standard deviations around the SMA.
+pub fn bollinger(prices: &[f64], period: usize, width: f64) -> (Vec<f64>, Vec<f64>) {
+    let mid = sma(prices, period);
+    let mut upper = Vec::with_capacity(prices.len());
+    let mut lower = Vec::with_capacity(prices.len());
+
+    for (i, mean) in mid.iter().enumerate() {
+        let window = window(prices, i, period);
+        let variance = window.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / window.len() as f64;
+        let deviation = variance.sqrt() * width;
+
+        upper.push(mean + deviation);
+        lower.push(mean - deviation);
+    }
+
+    (upper, lower)
+}
+
+/// Relative strength index from 0 to 100 using Wilder's smoothing.
+pub fn rsi(prices: &[f64], period: usize) -> Vec<f64> {
+    let mut values = Vec::with_capacity(prices.len());
+    let mut avg_gain = 0.0;
+    let mut avg_loss = 0.0;
+
+    for i in 0..prices.len() {
+        if i == 0 {
+            values.push(50.0);
+            continue;
+        }
+
+        let change = prices[i] - prices[i - 1];
+        let gain = change.max(0.0);
+        let loss = (-change).max(0.0);
+
+        // Plain average until a full period, smoothed after
+        let n = i.min(period) as f64;
+        avg_gain = (avg_gain * (n - 1.0) + gain) / n;
+        avg_loss = (avg_loss * (n - 1.0) + loss) / n;
+
+        let value = if avg_loss == 0.0 {
+            if avg_gain == 0.0 {
+                50.0
+            } else {
+                100.0
+            }
+        } else {
+            100.0 - 100.0 / (1.0 + avg_gain / avg_loss)
+        };
+        values.push(value);
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn sma_averages_the_partial_then_full_window() {
+        let values = sma(&[1.0, 2.0, 3.0, 4.0, 5.0], 3);
+
+        assert_eq!(values.len(), 5);
+        assert!(close(values[0], 1.0));
+        assert!(close(values[1], 1.5));
+        assert!(close(values[2], 2.0));
+        assert!(close(values[4], 4.0));
+    }
+
+    #[test]
+    fn ema_starts_at_the_first_price_and_follows_alpha() {
+        let values = ema(&[10.0, 20.0, 20.0], 3);
+
+        assert!(close(values[0], 10.0));
+        assert!(close(values[1], 15.0));
+        assert!(close(values[2], 17.5));
+    }
+
+    #[test]
+    fn bollinger_is_flat_without_variance() {
+        let (upper, lower) = bollinger(&[5.0; 6], 4, BOLLINGER_WIDTH);
+
+        assert!(upper.iter().all(|v| close(*v, 5.0)));
+        assert!(lower.iter().all(|v| close(*v, 5.0)));
+    }
+
+    #[test]
+    fn bollinger_bands_are_width_deviations_around_the_mean() {
+        let (upper, lower) = bollinger(&[1.0, 3.0], 2, 2.0);
+
+        // Mean 2, deviation 1
+        assert!(close(upper[1], 4.0));
+        assert!(close(lower[1], 0.0));
+    }
+
+    #[test]
+    fn rsi_saturates_on_one_way_moves() {
+        let rising: Vec<f64> = (0..20).map(f64::from).collect();
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+
+        assert!(close(rsi(&rising, RSI_PERIOD)[0], 50.0));
+        assert!(close(*rsi(&rising, RSI_PERIOD).last().unwrap(), 100.0));
+        assert!(close(*rsi(&falling, RSI_PERIOD).last().unwrap(), 0.0));
+        assert!(close(*rsi(&[3.0; 5], RSI_PERIOD).last().unwrap(), 50.0));
+    }
+
+    #[test]
+    fn rsi_balances_equal_gains_and_losses() {
+        let values = rsi(&[1.0, 2.0, 1.0], 2);
+
+        assert!(close(values[2], 50.0));
+    }
+}
@@ -0,0 +1,133 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::chain::NETWORK_BLOCK_SECONDS;
+
+pub const MAX_NETWORK_LEVEL: u64 = 5;
+
+/// Connection names and their latency in milliseconds by network level.
+pub const NETWORK_TIERS: [(&str, f64); 6] = [
+    ("Dial-up", 400.0),
+    ("DSL", 220.0),
+    ("Cable", 120.0),
+    ("Fiber", 60.0),
+    ("Datacenter", 25.0),
+    ("Pool Colocation", 8.0),
+];
+
+/// Shares lost to jitter and timeouts per millisecond of latency, on top of
+/// those that arrive after the block they were for.
+const REJECT_PER_MS: f64 = 0.00005;
+
+/// Stale and orphan chances are capped so a slow link never stops mining.
+const MAX_STALE_RATE: f64 = 0.5;
+
+pub fn network_name(level: u64) -> &'static str {
+    NETWORK_TIERS[level.min(MAX_NETWORK_LEVEL) as usize].0
+}
+
+pub fn network_latency_ms(level: u64) -> f64 {
+    NETWORK_TIERS[level.min(MAX_NETWORK_LEVEL) as usize].1
+}
+
+/// Chance a share is stale or rejected. A share is stale when the network
+/// finds the block it was for while it is still in flight.
+pub fn stale_rate(latency_ms: f64) -> f64 {
+    let rejected = latency_ms * REJECT_PER_MS;
+    let stale = in_flight_chance(latency_ms);
+
+    (1.0 - (1.0 - rejected) * (1.0 - stale)).min(MAX_STALE_RATE)
+}
+
+/// Chance a block loses the race against a rival block found while it was
+/// still propagating.
+pub fn orphan_rate(latency_ms: f64) -> f64 {
+    in_flight_chance(latency_ms).min(MAX_STALE_RATE)
+}
+
+/// Chance the network finds a block within `latency_ms`.
+fn in_flight_chance(latency_ms: f64) -> f64 {
+    1.0 - (-(latency_ms.max(0.0) / 1000.0) / NETWORK_BLOCK_SECONDS).exp()
+}
+
+/// Work lost to latency on a coin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct StaleStats {
+    pub stale_shares: f64,
+    pub orphaned_blocks: u64,
+}
+
+impl StaleStats {
+    /// Stale shares as a percent of every share found.
+    pub fn stale_percent(&self, found_shares: f64) -> f64 {
+        if found_shares <= 0.0 {
+            return 0.0;
+        }
+        self.stale_shares / found_shares * 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn faster_networks_lose_less_work() {
+        for level in 0..MAX_NETWORK_LEVEL {
+            let slow = network_latency_ms(level);
+            let fast = network_latency_ms(level + 1);
+
+            assert!(fast < slow);
+            assert!(stale_rate(fast) < stale_rate(slow));
+            assert!(orphan_rate(fast) < orphan_rate(slow));
+        }
+    }
+
+    #[test]
+    fn stale_shares_outnumber_orphans() {
+        for (_, latency) in NETWORK_TIERS {
+            assert!(stale_rate(latency) > orphan_rate(latency));
+        }
+    }
+
+    #[test]
+    fn no_latency_loses_nothing() {
+        assert_eq!(stale_rate(0.0), 0.0);
+        assert_eq!(orphan_rate(0.0), 0.0);
+        assert_eq!(orphan_rate(-10.0), 0.0);
+    }
+
+    #[test]
+    fn orphans_follow_the_network_block_interval() {
+        let latency = 500.0;
+        let expected = 1.0 - (-0.5 / NETWORK_BLOCK_SECONDS).exp();
+
+        assert!((orphan_rate(latency) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rates_are_capped() {
+        assert_eq!(stale_rate(1.0e9), MAX_STALE_RATE);
+        assert_eq!(orphan_rate(1.0e9), MAX_STALE_RATE);
+    }
+
+    #[test]
+    fn levels_past_the_top_use_the_best_tier() {
+        assert_eq!(network_name(MAX_NETWORK_LEVEL + 3), "Pool Colocation");
+        assert_eq!(
+            network_latency_ms(99),
+            network_latency_ms(MAX_NETWORK_LEVEL)
+        );
+    }
+
+    #[test]
+    fn stale_percent_of_found_shares() {
+        let stats = StaleStats {
+            stale_shares: 5.0,
+            orphaned_blocks: 0,
+        };
+
+        assert_eq!(stats.stale_percent(0.0), 0.0);
+        assert_eq!(stats.stale_percent(50.0), 10.0);
+    }
+}
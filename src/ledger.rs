@@ -0,0 +1,430 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::crypto_coin::CoinId;
+
+pub const MAX_LEDGER_ENTRIES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LedgerKind {
+    Mined,
+    BlockBonus,
+    Buy,
+    Sell,
+    PowerRefill,
+    Upgrade,
+    NftSale,
+    RugProtection,
+    RugPull,
+    Margin,
+    Interest,
+    Staking,
+    Swap,
+    Fork,
+}
+
+impl LedgerKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LedgerKind::Mined => "Mined",
+            LedgerKind::BlockBonus => "Block Bonus",
+            LedgerKind::Buy => "Buy",
+            LedgerKind::Sell => "Sell",
+            LedgerKind::PowerRefill => "Power",
+            LedgerKind::Upgrade => "Upgrade",
+            LedgerKind::NftSale => "NFT Sale",
+            LedgerKind::RugProtection => "Rug Protection",
+            LedgerKind::RugPull => "Rug Pull",
+            LedgerKind::Margin => "Margin",
+            LedgerKind::Interest => "Interest",
+            LedgerKind::Staking => "Staking",
+            LedgerKind::Swap => "Swap",
+            LedgerKind::Fork => "Fork Airdrop",
+        }
+    }
+
+    pub fn all() -> Vec<LedgerKind> {
+        vec![
+            LedgerKind::Mined,
+            LedgerKind::BlockBonus,
+            LedgerKind::Buy,
+            LedgerKind::Sell,
+            LedgerKind::PowerRefill,
+            LedgerKind::Upgrade,
+            LedgerKind::NftSale,
+            LedgerKind::RugProtection,
+            LedgerKind::RugPull,
+            LedgerKind::Margin,
+            LedgerKind::Interest,
+            LedgerKind::Staking,
+            LedgerKind::Swap,
+            LedgerKind::Fork,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub day: u64,
+    pub kind: LedgerKind,
+    pub coin: Option<String>,
+    pub coin_id: Option<CoinId>,
+    pub note: Option<String>,
+    pub amount: f64,
+    pub price: f64,
+    pub cash: f64,
+}
+
+impl LedgerEntry {
+    fn matches(&self, other: &LedgerEntry) -> bool {
+        self.day == other.day
+            && self.kind == other.kind
+            && self.coin_id == other.coin_id
+            && self.note == other.note
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CoinPosition {
+    pub coin: String,
+    pub id: Option<CoinId>,
+    pub amount: f64,
+    pub cost_basis: f64,
+    pub realized: f64,
+}
+
+impl CoinPosition {
+    pub fn new(id: CoinId, coin: &str) -> Self {
+        CoinPosition {
+            coin: coin.to_string(),
+            id: Some(id),
+            amount: 0.0,
+            cost_basis: 0.0,
+            realized: 0.0,
+        }
+    }
+
+    pub fn avg_cost(&self) -> f64 {
+        if self.amount <= 0.0 {
+            return 0.0;
+        }
+        self.cost_basis / self.amount
+    }
+
+    pub fn unrealized(&self, price: f64) -> f64 {
+        self.amount * price - self.cost_basis
+    }
+
+    fn add(&mut self, amount: f64, cost: f64) {
+        self.amount += amount;
+        self.cost_basis += cost;
+    }
+
+    fn remove(&mut self, amount: f64, proceeds: f64) -> f64 {
+        let amount = amount.min(self.amount).max(0.0);
+        let cost = self.avg_cost() * amount;
+        let pnl = proceeds - cost;
+
+        self.amount -= amount;
+        self.cost_basis -= cost;
+        self.realized += pnl;
+
+        if self.amount < 0.00001 {
+            self.amount = 0.0;
+            self.cost_basis = 0.0;
+        }
+
+        pnl
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    pub entries: Vec<LedgerEntry>,
+    pub positions: Vec<CoinPosition>,
+}
+
+impl Ledger {
+    pub fn new() -> Self {
+        Ledger {
+            entries: Vec::new(),
+            positions: Vec::new(),
+        }
+    }
+
+    /// Adds an entry, folding it into an existing entry for the same day, kind,
+    /// coin and note so that per-tick mining does not flood the ledger.
+    fn push(&mut self, entry: LedgerEntry) {
+        let existing = self
+            .entries
+            .iter_mut()
+            .rev()
+            .take_while(|e| e.day == entry.day)
+            .find(|e| e.matches(&entry));
+
+        if let Some(existing) = existing {
+            existing.amount += entry.amount;
+            existing.cash += entry.cash;
+            existing.price = entry.price;
+            return;
+        }
+
+        self.entries.push(entry);
+
+        if self.entries.len() > MAX_LEDGER_ENTRIES {
+            self.entries.remove(0);
+        }
+    }
+
+    fn position_mut(&mut self, id: CoinId, coin: &str) -> &mut CoinPosition {
+        let index = match self.positions.iter().position(|p| p.id == Some(id)) {
+            Some(index) => index,
+            None => {
+                self.positions.push(CoinPosition::new(id, coin));
+                self.positions.len() - 1
+            }
+        };
+
+        &mut self.positions[index]
+    }
+
+    pub fn position(&self, id: CoinId) -> Option<&CoinPosition> {
+        self.positions.iter().find(|p| p.id == Some(id))
+    }
+
+    /// Gives positions and entries from saves older than coin ids the id of
+    /// the coin with their name. Names are unique among `coins` at load.
+    pub fn migrate_coin_ids(&mut self, coins: &[(CoinId, String)]) {
+        let id_for = |name: &str| coins.iter().find(|(_, n)| n == name).map(|(id, _)| *id);
+
+        for position in self.positions.iter_mut().filter(|p| p.id.is_none()) {
+            position.id = id_for(&position.coin);
+        }
+
+        for entry in self.entries.iter_mut().filter(|e| e.coin_id.is_none()) {
+            entry.coin_id = entry.coin.as_deref().and_then(id_for);
+        }
+    }
+
+    pub fn record_mined(
+        &mut self,
+        day: u64,
+        id: CoinId,
+        coin: &str,
+        kind: LedgerKind,
+        amount: f64,
+        price: f64,
+    ) {
+        if amount <= 0.0 {
+            return;
+        }
+
+        // Mined coins enter the position at zero cost.
+        self.position_mut(id, coin).add(amount, 0.0);
+        self.push(LedgerEntry {
+            day,
+            kind,
+            coin: Some(coin.to_string()),
+            coin_id: Some(id),
+            note: None,
+            amount,
+            price,
+            cash: 0.0,
+        });
+    }
+
+    pub fn record_buy(&mut self, day: u64, id: CoinId, coin: &str, amount: f64, price: f64) {
+        let cost = amount * price;
+
+        self.position_mut(id, coin).add(amount, cost);
+        self.push(LedgerEntry {
+            day,
+            kind: LedgerKind::Buy,
+            coin: Some(coin.to_string()),
+            coin_id: Some(id),
+            note: None,
+            amount,
+            price,
+            cash: -cost,
+        });
+    }
+
+    pub fn record_sell(
+        &mut self,
+        day: u64,
+        id: CoinId,
+        coin: &str,
+        amount: f64,
+        price: f64,
+    ) -> f64 {
+        let proceeds = amount * price;
+
+        let pnl = self.position_mut(id, coin).remove(amount, proceeds);
+        self.push(LedgerEntry {
+            day,
+            kind: LedgerKind::Sell,
+            coin: Some(coin.to_string()),
+            coin_id: Some(id),
+            note: None,
+            amount: -amount,
+            price,
+            cash: proceeds,
+        });
+
+        pnl
+    }
+
+    /// A swap realizes the coin given up at its price, the coin received takes
+    /// that value as its cost.
+    pub fn record_swap(
+        &mut self,
+        day: u64,
+        from: (CoinId, &str, f64, f64),
+        to: (CoinId, &str, f64, f64),
+    ) -> f64 {
+        let (from_id, from_coin, amount_in, from_price) = from;
+        let (to_id, to_coin, amount_out, to_price) = to;
+        let value = amount_in * from_price;
+
+        let pnl = self
+            .position_mut(from_id, from_coin)
+            .remove(amount_in, value);
+        self.push(LedgerEntry {
+            day,
+            kind: LedgerKind::Swap,
+            coin: Some(from_coin.to_string()),
+            coin_id: Some(from_id),
+            note: Some(format!("To {to_coin}")),
+            amount: -amount_in,
+            price: from_price,
+            cash: 0.0,
+        });
+
+        self.position_mut(to_id, to_coin).add(amount_out, value);
+        self.push(LedgerEntry {
+            day,
+            kind: LedgerKind::Swap,
+            coin: Some(to_coin.to_string()),
+            coin_id: Some(to_id),
+            note: Some(format!("From {from_coin}")),
+            amount: amount_out,
+            price: to_price,
+            cash: 0.0,
+        });
+
+        pnl
+    }
+
+    pub fn record_expense(&mut self, day: u64, kind: LedgerKind, note: &str, cost: f64) {
+        self.push(LedgerEntry {
+            day,
+            kind,
+            coin: None,
+            coin_id: None,
+            note: Some(note.to_string()),
+            amount: 0.0,
+            price: 0.0,
+            cash: -cost,
+        });
+    }
+
+    pub fn record_income(&mut self, day: u64, kind: LedgerKind, note: &str, cash: f64) {
+        self.push(LedgerEntry {
+            day,
+            kind,
+            coin: None,
+            coin_id: None,
+            note: Some(note.to_string()),
+            amount: 0.0,
+            price: 0.0,
+            cash,
+        });
+    }
+
+    /// Coins given up for nothing, like an early unstake penalty.
+    pub fn record_forfeit(
+        &mut self,
+        day: u64,
+        id: CoinId,
+        coin: &str,
+        kind: LedgerKind,
+        amount: f64,
+        price: f64,
+    ) {
+        if amount <= 0.0 {
+            return;
+        }
+
+        self.position_mut(id, coin).remove(amount, 0.0);
+        self.push(LedgerEntry {
+            day,
+            kind,
+            coin: Some(coin.to_string()),
+            coin_id: Some(id),
+            note: None,
+            amount: -amount,
+            price,
+            cash: 0.0,
+        });
+    }
+
+    pub fn record_rug_pull(
+        &mut self,
+        day: u64,
+        id: CoinId,
+        coin: &str,
+        protected_amount: f64,
+        protected_value: f64,
+        price: f64,
+    ) {
+        if protected_amount > 0.0 {
+            self.position_mut(id, coin)
+                .remove(protected_amount, protected_value);
+            self.push(LedgerEntry {
+                day,
+                kind: LedgerKind::RugProtection,
+                coin: Some(coin.to_string()),
+                coin_id: Some(id),
+                note: None,
+                amount: -protected_amount,
+                price,
+                cash: protected_value,
+            });
+        }
+
+        let position = self.position_mut(id, coin);
+        let lost_amount = position.amount;
+
+        if lost_amount > 0.0 {
+            position.remove(lost_amount, 0.0);
+            self.push(LedgerEntry {
+                day,
+                kind: LedgerKind::RugPull,
+                coin: Some(coin.to_string()),
+                coin_id: Some(id),
+                note: None,
+                amount: -lost_amount,
+                price: 0.0,
+                cash: 0.0,
+            });
+        }
+    }
+
+    pub fn total_realized(&self) -> f64 {
+        self.positions.iter().map(|p| p.realized).sum()
+    }
+
+    pub fn query(&self, coin: Option<CoinId>, kind: Option<LedgerKind>) -> Vec<LedgerEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| coin.is_none() || e.coin_id == coin)
+            .filter(|e| kind.is_none() || Some(e.kind) == kind)
+            .cloned()
+            .collect()
+    }
+
+    pub fn entries_for_coin(&self, coin: CoinId) -> Vec<LedgerEntry> {
+        self.query(Some(coin), None)
+    }
+}
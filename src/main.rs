@@ -2,7 +2,7 @@
 
 use dioxus::html::input_data::MouseButton;
 use dioxus::prelude::*;
-use dioxus_charts::LineChart;
+use dioxus_charts::{BarChart, LineChart};
 use dioxus_logger::tracing::{info, Level};
 use gloo_timers::future::TimeoutFuture;
 use gloo_utils::window;
@@ -19,27 +19,67 @@ use i_db::{
     GalaxyResponseQueue, GalaxySaveList, GameState, SelectionMultiList,
 };
 
+mod alerts;
+mod allocation;
+mod archetype;
+mod auto_mining;
+mod chain;
+mod coin_identity;
 mod crypto_coin;
+mod discovery;
+mod fork;
 mod galaxy_api;
+mod ico;
+mod indicators;
+mod latency;
+mod ledger;
+mod margin;
 mod market;
 mod mining_rig;
+mod net_worth;
+mod news;
 mod nft;
+mod npc;
+mod pool;
+mod price_history;
+mod rug_risk;
+mod sentiment;
+mod staking;
+mod supply;
+mod swap;
 mod utils;
 
-use crypto_coin::CryptoCoin;
+use alerts::{daily_change, AlertRule};
+use allocation::HashAllocation;
+use auto_mining::{auto_mining_slots, rerank_interval, MAX_AUTO_MINING_LEVEL};
+use crypto_coin::{CoinId, CryptoCoin};
 use galaxy_api::{
     delete_cloud_save, do_cloud_save, fetch_save_list, find_save_slot, galaxy_info,
     galaxy_response, get_galaxy_save_data,
 };
+use ico::IcoOffering;
+use indicators::{
+    bollinger, ema, rsi, sma, IndicatorSet, BOLLINGER_PERIOD, BOLLINGER_WIDTH, EMA_PERIOD,
+    RSI_PERIOD, SMA_PERIOD,
+};
+use latency::{network_latency_ms, network_name, MAX_NETWORK_LEVEL};
+use ledger::LedgerKind;
+use margin::{MarginAccount, MarginKind, MarginPosition, LEVERAGE_OPTIONS};
 use market::{
-    cull_market, gen_random_coin_with_set_index, replace_coin, GAME_TIME, MARKET,
+    cull_market, gen_random_coin_with_set_index, replace_coin, run_forks, GAME_TIME, MARKET,
     MAX_SERIES_LENGTH, SELECTION,
 };
 use mining_rig::MINING_RIG;
+use pool::PoolScheme;
+use price_history::{ChartZoom, PriceCandle, MAX_DAILY_CANDLES};
+use rug_risk::MAX_DUE_DILIGENCE_LEVEL;
+use staking::{Stake, StakeTerm, EARLY_UNSTAKE_PENALTY};
+use supply::CoinSort;
+use swap::SWAP_SPREAD;
 use utils::{
-    command_line_output, BuyModal, CanvasSize, CatchupModal, ConfirmModal, DoSave,
-    GalaxyLoadingModal, GalaxySaveDetails, GameTime, HelpModal, ImportExportModal, PaintUndo,
-    Paused, Position, TpsCounter, WelcomeModal,
+    command_line_output, request_notification_permission, truncate_price, BuyModal, CanvasSize,
+    CatchupModal, CoinDetailModal, ConfirmModal, DoSave, GalaxyLoadingModal, GalaxySaveDetails,
+    GameTime, HelpModal, ImportExportModal, PaintUndo, Paused, Position, TpsCounter, WelcomeModal,
 };
 
 use nft::NftStudio;
@@ -53,6 +93,7 @@ static CATCHUP_MODAL: GlobalSignal<CatchupModal> = Signal::global(|| CatchupModa
 static HELP_MODAL: GlobalSignal<HelpModal> = Signal::global(|| HelpModal::default());
 static WELCOME_MODAL: GlobalSignal<WelcomeModal> = Signal::global(|| WelcomeModal::default());
 static BUY_MODAL: GlobalSignal<BuyModal> = Signal::global(|| BuyModal::default());
+static COIN_DETAIL_MODAL: GlobalSignal<CoinDetailModal> = Signal::global(CoinDetailModal::default);
 static IMPORT_EXPORT_MODAL: GlobalSignal<ImportExportModal> =
     Signal::global(|| ImportExportModal::default());
 static GALAXY_LOADING_MODAL: GlobalSignal<GalaxyLoadingModal> =
@@ -228,11 +269,15 @@ fn App() -> Element {
                         Chart { labels, series, series_labels }
                     }
                     div { class: "flex-1", CommandLine {} }
+                    div { class: "flex-1", NewsTicker {} }
+                    div { class: "flex-1", IcoCalendar {} }
+                    div { class: "flex-1", Notifications {} }
                 }
                 div { class: "grid grid-cols-1 w-full gap-4 order-4",
                     div { class: "flex-1",
                         Paint { confirm_modal: confirm_modal.clone() }
                     }
+                    div { class: "flex-1", Portfolio {} }
                 }
             }
             Footer {}
@@ -247,6 +292,7 @@ fn App() -> Element {
             labels: labels.clone(),
             confirm_modal: confirm_modal.clone()
         }
+        CoinDetailModal {}
         ImportExportModal { series_labels: series_labels.clone(), series: series.clone(), labels: labels.clone() }
         GalaxyLoadingModal {}
         ConfirmModal { confirm_modal: confirm_modal.clone() }
@@ -260,6 +306,7 @@ fn Coins(
     labels: Signal<Vec<String>>,
 ) -> Element {
     let mut show_inactive = use_signal(|| false);
+    let mut coin_sort = use_signal(|| CoinSort::Slot);
 
     let toggel_inactive = {
         move |_| {
@@ -312,6 +359,31 @@ fn Coins(
                 }
                 div { class: "window-body", style: "overflow: auto;",
 
+                    div {
+                        class: "flex flex-row",
+                        style: "justify-content: end;align-items: center;gap: 6px;margin-bottom: 6px;",
+                        label { r#for: "coin-sort", "Sort by" }
+                        select {
+                            id: "coin-sort",
+                            class: "select",
+                            onchange: move |e| {
+                                if let Some(sort) = CoinSort::all()
+                                    .into_iter()
+                                    .find(|s| s.label() == e.value())
+                                {
+                                    coin_sort.set(sort);
+                                }
+                            },
+                            for sort in CoinSort::all() {
+                                option {
+                                    value: "{sort.label()}",
+                                    selected: sort == coin_sort(),
+                                    "{sort.label()}"
+                                }
+                            }
+                        }
+                    }
+
                     div { class: "sunken-panel", style: "",
 
                         table { class: "interactive w-full noselect",
@@ -321,6 +393,7 @@ fn Coins(
                                     th { "Coin" }
                                     th { "Curent Price" }
                                     th { "Balance" }
+                                    th { "Mkt Cap" }
                                     th { "$ / Min" }
                                     th { "Age" }
                                     th { "Market" }
@@ -330,16 +403,14 @@ fn Coins(
                                 id: "coins-table",
                                 class: "p-5",
                                 style: "height: 262px; overflow: auto;",
-                                for coin in MARKET().index_sorted_coins(show_inactive()) {
+                                for coin in MARKET().sorted_coins(show_inactive(), coin_sort()) {
                                     tr {
-                                        id: format!("{}-row", coin.name),
+                                        id: coin.id().row_id(),
                                         onclick: {
                                             let coin = coin.clone();
                                             move |_| {
-                                                let coin_name = coin.name.clone();
-                                                let coin_index = coin.index;
                                                 info!("selections {:?}", SELECTION().clone());
-                                                SELECTION.write().make_selection(coin_index, &coin_name, true);
+                                                SELECTION.write().make_selection(&coin, true);
                                                 DO_SAVE.write().save = true;
                                             }
                                         },
@@ -349,23 +420,81 @@ fn Coins(
                                                 style: "position:relative;top:-5px;",
                                                 input {
                                                     class: "",
-                                                    id: coin.clone().name,
+                                                    id: coin.id().radio_id(),
                                                     r#type: "radio",
                                                     name: "coin-selection",
-                                                    value: "{coin.name}"
+                                                    value: "{coin.id()}"
                                                 }
                                                 label {
                                                     class: "",
-                                                    r#for: coin.clone().name
+                                                    r#for: coin.id().radio_id()
+                                                }
+                                            }
+                                        }
+                                        td { style: "padding: 3px;",
+                                            div { class: "flex flex-row", style: "align-items: center;gap: 4px;",
+                                                if coin.active {
+                                                    span {
+                                                        class: "coin-watch",
+                                                        title: "Watch",
+                                                        onclick: {
+                                                            let id = coin.id();
+                                                            move |e: Event<MouseData>| {
+                                                                e.stop_propagation();
+                                                                MARKET.write().alerts_mut().toggle_watch(id);
+                                                                DO_SAVE.write().save = true;
+                                                            }
+                                                        },
+                                                        if MARKET().alerts().is_watched(coin.id()) {
+                                                            "★"
+                                                        } else {
+                                                            "☆"
+                                                        }
+                                                    }
+                                                }
+                                                CoinIcon { coin: coin.clone() }
+                                                p { title: "{coin.get_pitch()}", "{coin.name}" }
+                                            }
+                                            p { class: "coin-hint", "{coin.get_ticker()}" }
+                                            p {
+                                                class: "coin-hint",
+                                                title: "Coin profile hint",
+                                                "{coin.hint()}"
+                                            }
+                                            for signal in coin.rug_signals(MINING_RIG().get_due_diligence_level()) {
+                                                p { class: "coin-warning", title: "Rug pull warning sign",
+                                                    "⚠ {signal.label()}"
+                                                }
+                                            }
+                                            if MINING_RIG().get_due_diligence_level() >= MAX_DUE_DILIGENCE_LEVEL {
+                                                if let Some(days_left) = coin.rug_days_left() {
+                                                    p { class: "coin-warning", "⚠ Rug expected in ~{days_left:.1} days" }
                                                 }
                                             }
                                         }
-                                        td { style: "padding: 3px;", "{coin.name}" }
                                         td { style: "padding: 3px;",
                                             "${format_comma_seperator(coin.current_price, 2)}"
                                         }
                                         td { style: "padding: 3px;font-family: 'Courier New', Courier, monospace;",
                                             "{format_comma_seperator(coin.balance,5)}"
+                                            if let Some(stake) = &coin.stake {
+                                                p { class: "coin-hint",
+                                                    "Staked {format_comma_seperator(stake.amount, 5)} | APY {format_comma_seperator(stake.apy() * 100.0, 1)}% | matures day {stake.maturity_day()}"
+                                                }
+                                            }
+                                        }
+                                        td { style: "padding: 3px;",
+                                            title: "Circulating {format_compact(coin.circulating_supply())} of {format_compact(coin.total_supply())}",
+                                            if let Some(rank) = MARKET().cap_rank(&coin) {
+                                                "#{rank} "
+                                            }
+                                            "${format_compact(coin.market_cap())}"
+                                            p { class: "coin-hint",
+                                                "Vol ${format_compact(coin.volume_24h())}"
+                                            }
+                                            p { class: "coin-hint",
+                                                "Dom {format_comma_seperator(MARKET().dominance(&coin) * 100.0, 1)}%"
+                                            }
                                         }
                                         td { style: "padding: 3px;", "${profit_value(&coin)}" }
                                         td { style: "padding: 3px;", "{coin.get_age()}" }
@@ -384,14 +513,40 @@ fn Coins(
                                                         },
                                                         "Market"
                                                     }
+                                                    button {
+                                                        class: "sell-btn",
+                                                        onclick: {
+                                                            let id = coin.id();
+                                                            move |event: Event<MouseData>| {
+                                                                event.stop_propagation();
+                                                                COIN_DETAIL_MODAL.write().coin = Some(id);
+                                                                COIN_DETAIL_MODAL.write().show = true;
+                                                            }
+                                                        },
+                                                        "Info"
+                                                    }
                                                 }
                                             }
                                         } else {
                                             td { style: "padding: 3px;",
-                                                button {
-                                                    disabled: true,
-                                                    class: "sell-btn",
-                                                    "Market"
+                                                div { class: "flex flex-row justify-center",
+                                                    button {
+                                                        disabled: true,
+                                                        class: "sell-btn",
+                                                        "Market"
+                                                    }
+                                                    button {
+                                                        class: "sell-btn",
+                                                        onclick: {
+                                                            let id = coin.id();
+                                                            move |event: Event<MouseData>| {
+                                                                event.stop_propagation();
+                                                                COIN_DETAIL_MODAL.write().coin = Some(id);
+                                                                COIN_DETAIL_MODAL.write().show = true;
+                                                            }
+                                                        },
+                                                        "Info"
+                                                    }
                                                 }
                                             }
                                         }
@@ -447,6 +602,255 @@ fn Coins(
     }
 }
 
+#[component]
+pub fn CoinIcon(coin: CryptoCoin) -> Element {
+    rsx! {
+        if let Some(logo) = coin.logo {
+            svg {
+                class: "coin-icon",
+                width: "15",
+                height: "15",
+                view_box: "0 0 5 5",
+                for (x , y) in logo.filled_cells() {
+                    rect {
+                        x: "{x}",
+                        y: "{y}",
+                        width: "1",
+                        height: "1",
+                        fill: logo.color()
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn Portfolio() -> Element {
+    let mut selected_tab: Signal<String> = use_signal(|| "positions".to_string());
+    let mut coin_filter: Signal<String> = use_signal(|| "all".to_string());
+    let mut kind_filter: Signal<String> = use_signal(|| "all".to_string());
+
+    let ledger = MARKET().ledger();
+
+    let coin_price = |id: Option<CoinId>| {
+        let mkt = MARKET();
+        match id.and_then(|id| mkt.coin_by_id(id)) {
+            Some(coin) if coin.active => coin.current_price,
+            _ => 0.0,
+        }
+    };
+
+    let positions = {
+        let mut positions = ledger.positions.clone();
+        positions.retain(|p| p.amount > 0.0 || p.realized != 0.0);
+        positions.sort_by(|a, b| b.realized.total_cmp(&a.realized));
+        positions
+    };
+
+    let total_unrealized: f64 = positions
+        .iter()
+        .map(|p| p.unrealized(coin_price(p.id)))
+        .sum();
+
+    let total_realized = ledger.total_realized();
+
+    let entries = {
+        let coin = coin_filter().parse::<u64>().ok().map(CoinId);
+
+        let kind = kind_filter()
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| LedgerKind::all().get(i).copied());
+
+        ledger.query(coin, kind)
+    };
+
+    let ledger_coins = {
+        let mut coins: Vec<(CoinId, String)> = ledger
+            .positions
+            .iter()
+            .filter_map(|p| p.id.map(|id| (id, p.coin.clone())))
+            .collect();
+        coins.sort_by(|a, b| a.1.cmp(&b.1));
+        coins
+    };
+
+    let pnl_style = |v: f64| {
+        if v < 0.0 {
+            "padding: 3px;color: #a00;"
+        } else {
+            "padding: 3px;color: #070;"
+        }
+    };
+
+    rsx! {
+        div { class: "items-center justify-center container",
+            div { class: "aspect-w-1 aspect-h-1 overflow-hidden window h-fit",
+                div { class: "title-bar",
+                    div { class: "title-bar-text", "Portfolio" }
+                    div { class: "title-bar-controls",
+                        button {
+                            class: "close",
+                            aria_label: "Close",
+                            onclick: |_| {
+                                info!("Closing window");
+                            },
+                            ""
+                        }
+                    }
+                }
+                div { class: "window-body",
+                    menu { role: "tablist", class: "noselect",
+                        li {
+                            role: "tab",
+                            aria_selected: if selected_tab() == "positions" { "true" } else { "false" },
+                            style: "padding:5px;padding-left:10px;padding-right:10px;",
+                            onclick: move |_| selected_tab.set("positions".to_string()),
+                            p { class: "rig-tab", "Positions" }
+                        }
+                        li {
+                            role: "tab",
+                            aria_selected: if selected_tab() == "dashboard" { "true" } else { "false" },
+                            style: "padding:5px;padding-left:10px;padding-right:10px;",
+                            onclick: move |_| selected_tab.set("dashboard".to_string()),
+                            p { class: "rig-tab", "Dashboard" }
+                        }
+                        li {
+                            role: "tab",
+                            aria_selected: if selected_tab() == "ledger" { "true" } else { "false" },
+                            style: "padding:5px;padding-left:10px;padding-right:10px;",
+                            onclick: move |_| selected_tab.set("ledger".to_string()),
+                            p { class: "rig-tab", "Ledger" }
+                        }
+                    }
+
+                    if selected_tab() == "positions" {
+                        div { class: "window", role: "tabpanel", style: "padding: 10px;",
+                            div {
+                                class: "sunken-panel",
+                                style: "height: 200px; overflow: auto;",
+                                table { class: "interactive w-full noselect",
+                                    thead {
+                                        tr {
+                                            th { "Coin" }
+                                            th { "Held" }
+                                            th { "Avg Cost" }
+                                            th { "Realized" }
+                                            th { "Unrealized" }
+                                        }
+                                    }
+                                    tbody {
+                                        for position in positions.iter() {
+                                            tr {
+                                                td { style: "padding: 3px;", "{position.coin}" }
+                                                td { style: "padding: 3px;font-family: 'Courier New', Courier, monospace;",
+                                                    "{format_comma_seperator(position.amount, 5)}"
+                                                }
+                                                td { style: "padding: 3px;",
+                                                    "${format_comma_seperator(position.avg_cost(), 2)}"
+                                                }
+                                                td { style: pnl_style(position.realized),
+                                                    "${format_comma_seperator(position.realized, 2)}"
+                                                }
+                                                td { style: pnl_style(position.unrealized(coin_price(position.id))),
+                                                    "${format_comma_seperator(position.unrealized(coin_price(position.id)), 2)}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    if selected_tab() == "dashboard" {
+                        PortfolioDashboard {}
+                    }
+
+                    if selected_tab() == "ledger" {
+                        div { class: "window", role: "tabpanel", style: "padding: 10px;",
+                            div {
+                                class: "flex flex-row",
+                                style: "justify-content: space-between;margin-bottom: 10px;",
+                                select {
+                                    class: "select",
+                                    onchange: move |e| coin_filter.set(e.value()),
+                                    option { value: "all", selected: coin_filter() == "all", "All Coins" }
+                                    for (id , name) in ledger_coins.iter() {
+                                        option {
+                                            value: "{id.0}",
+                                            selected: id.0.to_string() == coin_filter(),
+                                            "{name}"
+                                        }
+                                    }
+                                }
+                                select {
+                                    class: "select",
+                                    onchange: move |e| kind_filter.set(e.value()),
+                                    option { value: "all", selected: kind_filter() == "all", "All Types" }
+                                    for (i , kind) in LedgerKind::all().iter().enumerate() {
+                                        option {
+                                            value: "{i}",
+                                            selected: i.to_string() == kind_filter(),
+                                            "{kind.label()}"
+                                        }
+                                    }
+                                }
+                            }
+                            div {
+                                class: "sunken-panel",
+                                style: "height: 200px; overflow: auto;",
+                                table { class: "interactive w-full noselect",
+                                    thead {
+                                        tr {
+                                            th { "Day" }
+                                            th { "Type" }
+                                            th { "Item" }
+                                            th { "Amount" }
+                                            th { "Cash" }
+                                        }
+                                    }
+                                    tbody {
+                                        for entry in entries.iter() {
+                                            tr {
+                                                td { style: "padding: 3px;", "{entry.day}" }
+                                                td { style: "padding: 3px;", "{entry.kind.label()}" }
+                                                td { style: "padding: 3px;",
+                                                    "{entry.coin.clone().or(entry.note.clone()).unwrap_or_default()}"
+                                                }
+                                                td { style: "padding: 3px;font-family: 'Courier New', Courier, monospace;",
+                                                    "{format_comma_seperator(entry.amount, 5)}"
+                                                }
+                                                td { style: pnl_style(entry.cash),
+                                                    "${format_comma_seperator(entry.cash, 2)}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    div { class: "status-bar", style: "margin-top:10px;",
+                        p {
+                            class: "status-bar-field p-1 font-mono p-2",
+                            style: "padding:4px;",
+                            "Realized: ${format_comma_seperator(total_realized, 2)}"
+                        }
+                        p {
+                            class: "status-bar-field p-1 font-mono p-2",
+                            style: "padding:4px;",
+                            "Unrealized: ${format_comma_seperator(total_unrealized, 2)}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 #[component]
 pub fn Footer() -> Element {
     let current_year = {
@@ -581,6 +985,36 @@ pub fn Upgrades() -> Element {
         }
     };
 
+    let get_research_tab_class = {
+        if MARKET().bank.balance > MINING_RIG().get_due_diligence_upgrade_cost()
+            && MINING_RIG().get_due_diligence_level() < MAX_DUE_DILIGENCE_LEVEL
+        {
+            "rig-tab upgradeable"
+        } else {
+            "rig-tab"
+        }
+    };
+
+    let get_auto_mining_tab_class = {
+        if MARKET().bank.balance > MINING_RIG().get_auto_mining_upgrade_cost()
+            && MINING_RIG().get_auto_mining_level() < MAX_AUTO_MINING_LEVEL
+        {
+            "rig-tab upgradeable"
+        } else {
+            "rig-tab"
+        }
+    };
+
+    let get_network_tab_class = {
+        if MARKET().bank.balance > MINING_RIG().get_network_upgrade_cost()
+            && MINING_RIG().get_network_level() < MAX_NETWORK_LEVEL
+        {
+            "rig-tab upgradeable"
+        } else {
+            "rig-tab"
+        }
+    };
+
     let get_multimining_tab_class = {
         if MARKET().bank.balance > SELECTION().get_upgrade_cost() && SELECTION().max_selectable < 10
         {
@@ -641,6 +1075,28 @@ pub fn Upgrades() -> Element {
                             }
                         }
 
+                        if MINING_RIG().get_level() >= 5 {
+                            li {
+                                id: "research-tab",
+                                role: "tab",
+                                aria_selected: if selected_tab() == "research" { "true" } else { "false" },
+                                style: "padding:5px;padding-left:10px;padding-right:10px;",
+                                onclick: move |_| selected_tab.set("research".to_string()),
+                                p { class: get_research_tab_class, "Research" }
+                            }
+                        }
+
+                        if MINING_RIG().get_level() >= 5 {
+                            li {
+                                id: "network-tab",
+                                role: "tab",
+                                aria_selected: if selected_tab() == "network" { "true" } else { "false" },
+                                style: "padding:5px;padding-left:10px;padding-right:10px;",
+                                onclick: move |_| selected_tab.set("network".to_string()),
+                                p { class: get_network_tab_class, "Network" }
+                            }
+                        }
+
                         if MINING_RIG().get_level() >= 35 {
                             li {
                                 id: "asic-tab",
@@ -652,7 +1108,18 @@ pub fn Upgrades() -> Element {
                             }
                         }
 
-                        if !MINING_RIG().get_global_share_cooldown() {
+                        if MINING_RIG().get_level() >= 15 {
+                            li {
+                                id: "auto-mining-tab",
+                                role: "tab",
+                                aria_selected: if selected_tab() == "auto-mining" { "true" } else { "false" },
+                                style: "padding:5px;padding-left:10px;padding-right:10px;",
+                                onclick: move |_| selected_tab.set("auto-mining".to_string()),
+                                p { class: get_auto_mining_tab_class, "Auto-Mine" }
+                            }
+                        }
+
+                        if MINING_RIG().get_multi_mining_unlocked() {
                             li {
                                 id: "multi-mining-tab",
                                 role: "tab",
@@ -672,6 +1139,14 @@ pub fn Upgrades() -> Element {
                         RigGPUTab { selected_tab }
                     }
 
+                    if MINING_RIG().get_level() >= 5 {
+                        RigResearchTab { selected_tab }
+                    }
+
+                    if MINING_RIG().get_level() >= 5 {
+                        RigNetworkTab { selected_tab }
+                    }
+
                     if MINING_RIG().get_level() >= 10 {
                         RigRugProtectionTab { selected_tab }
                     }
@@ -680,7 +1155,11 @@ pub fn Upgrades() -> Element {
                         RigAsicTab { selected_tab }
                     }
 
-                    if !MINING_RIG().get_global_share_cooldown() {
+                    if MINING_RIG().get_level() >= 15 {
+                        RigAutoMiningTab { selected_tab }
+                    }
+
+                    if MINING_RIG().get_multi_mining_unlocked() {
                         RigMultiMiningTab { selected_tab }
                     }
                 }
@@ -690,11 +1169,10 @@ pub fn Upgrades() -> Element {
 }
 
 #[component]
-pub fn RigMultiMiningTab(selected_tab: Signal<String>) -> Element {
+pub fn RigNetworkTab(selected_tab: Signal<String>) -> Element {
     let get_style = {
-        let selected_tab = selected_tab.clone();
         move || {
-            if selected_tab() == "multi-mining" {
+            if selected_tab() == "network" {
                 "display: block;padding: 10px;"
             } else {
                 "display: none;padding: 10px;"
@@ -703,9 +1181,8 @@ pub fn RigMultiMiningTab(selected_tab: Signal<String>) -> Element {
     };
 
     let get_style_buttons = {
-        let selected_tab = selected_tab.clone();
         move || {
-            if selected_tab() == "multi-mining" {
+            if selected_tab() == "network" {
                 "display: flex;justify-content: space-between;margin-top: 10px;"
             } else {
                 "display: none;justify-content: space-between;"
@@ -713,21 +1190,35 @@ pub fn RigMultiMiningTab(selected_tab: Signal<String>) -> Element {
         }
     };
 
-    let upgrade_available = {
-        if MARKET().bank.balance > SELECTION().get_upgrade_cost() && SELECTION().max_selectable < 10
-        {
-            false
-        } else {
-            true
-        }
-    };
+    let level = MINING_RIG().get_network_level();
+    let cost = MINING_RIG().get_network_upgrade_cost();
+    let latency = MINING_RIG().get_network_latency_ms();
 
-    let get_upgrade_cost = || {
-        if SELECTION().max_selectable >= 10 {
-            "Max Level".to_string()
-        } else {
-            format_comma_seperator(SELECTION().get_upgrade_cost(), 0)
+    let upgrade_disabled = MARKET().bank.balance < cost || level >= MAX_NETWORK_LEVEL;
+
+    // Stale work on the coins being mined right now
+    let stale_coins = SELECTION()
+        .get_selected()
+        .iter()
+        .filter_map(|s| MARKET().coin_by_id(s.coin_id()).cloned())
+        .collect::<Vec<CryptoCoin>>();
+
+    let do_upgrade = move |_| {
+        if MARKET.write().spend(cost, LedgerKind::Upgrade, "Network") {
+            MINING_RIG.write().upgrade_network();
+            MARKET.write().set_profit_factor(&SELECTION());
+
+            let level = MINING_RIG().get_network_level();
+            let msg = format!(
+                "Network upgraded to {}, latency {}ms",
+                network_name(level),
+                network_latency_ms(level)
+            );
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
         }
+        DO_SAVE.write().save = true;
     };
 
     rsx! {
@@ -736,26 +1227,264 @@ pub fn RigMultiMiningTab(selected_tab: Signal<String>) -> Element {
                 class: "flex flex-row",
                 style: "justify-content: space-between;",
                 div {
-                    h4 { "Multi Mining" }
-                    p { "Split the hashrate of your rig between multiple coins." }
+                    h4 { "Network" }
+                    p { "Slow links lose shares and blocks to the network." }
                     br {}
-                    p { "Level: {SELECTION().max_selectable}" }
+                    p { "Connection: {network_name(level)}" }
+                    p { "Latency: {latency}ms" }
                 }
                 div {
-                    h4 { "Mining Upgrade" }
+                    h4 { "Network Upgrade" }
                     br {}
-                    p { "Upgrade Cost:" }
-                    p { "${get_upgrade_cost()}" }
+                    if level < MAX_NETWORK_LEVEL {
+                        p { "Next: {network_name(level + 1)} ({network_latency_ms(level + 1)}ms)" }
+                        p { "Upgrade Cost: ${format_comma_seperator(cost, 2)}" }
+                    } else {
+                        p { "Max Level" }
+                    }
+                }
+            }
+            for coin in stale_coins {
+                p { class: "coin-hint",
+                    "{coin.name}: {format_comma_seperator(coin.get_stale_rate() * 100.0, 1)}% stale now, {format_comma_seperator(coin.stale_stats().stale_percent(coin.discovery().found_shares), 1)}% overall | {coin.stale_stats().orphaned_blocks} orphaned blocks"
                 }
             }
         }
 
-        div { class: "flex flex", style: get_style_buttons(),
+        div { class: "flex flex-row", style: get_style_buttons(),
             button {
                 class: "",
-                disabled: upgrade_available,
+                disabled: upgrade_disabled,
+                onclick: do_upgrade,
+                "Upgrade Network"
+            }
+        }
+    }
+}
+
+#[component]
+pub fn RigAutoMiningTab(selected_tab: Signal<String>) -> Element {
+    let get_style = {
+        move || {
+            if selected_tab() == "auto-mining" {
+                "display: block;padding: 10px;"
+            } else {
+                "display: none;padding: 10px;"
+            }
+        }
+    };
+
+    let get_style_buttons = {
+        move || {
+            if selected_tab() == "auto-mining" {
+                "display: flex;justify-content: space-between;margin-top: 10px;"
+            } else {
+                "display: none;justify-content: space-between;"
+            }
+        }
+    };
+
+    let level = MINING_RIG().get_auto_mining_level();
+    let cost = MINING_RIG().get_auto_mining_upgrade_cost();
+    let miner = MINING_RIG().auto_miner();
+
+    let upgrade_disabled = MARKET().bank.balance < cost || level >= MAX_AUTO_MINING_LEVEL;
+
+    let interval_minutes = rerank_interval(level) * 15;
+    let slots = auto_mining_slots(level, SELECTION().max_selectable);
+
+    let do_upgrade = move |_| {
+        if MARKET
+            .write()
+            .spend(cost, LedgerKind::Upgrade, "Auto-Mining")
+        {
+            MINING_RIG.write().upgrade_auto_mining();
+
+            let msg = format!(
+                "Auto-Mining upgraded, new level {}",
+                MINING_RIG().get_auto_mining_level()
+            );
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+        DO_SAVE.write().save = true;
+    };
+
+    rsx! {
+        div { class: "window", style: get_style(), role: "tabpanel",
+            div {
+                class: "flex flex-row",
+                style: "justify-content: space-between;",
+                div {
+                    h4 { "Auto-Mining" }
+                    p { "Switches your rig to the most profitable coins." }
+                    br {}
+                    p { "Level: {level}" }
+                    if level > 0 {
+                        p { "Re-ranks every {interval_minutes} game minutes" }
+                        p { "Mines up to {slots} coin(s)" }
+                    }
+                }
+                div {
+                    h4 { "Auto-Mining Upgrade" }
+                    br {}
+                    if level < MAX_AUTO_MINING_LEVEL {
+                        p { "Upgrade Cost: ${format_comma_seperator(cost, 2)}" }
+                    } else {
+                        p { "Max Level" }
+                    }
+                }
+            }
+            if level > 0 {
+                div { style: "margin-top: 10px;",
+                    div {
+                        input {
+                            id: "auto-mining-active",
+                            r#type: "checkbox",
+                            checked: miner.active,
+                            onchange: move |_| {
+                                MINING_RIG.write().toggle_auto_mining();
+                                DO_SAVE.write().save = true;
+                            }
+                        }
+                        label { r#for: "auto-mining-active", "Enable Auto-Mining" }
+                    }
+                    div {
+                        input {
+                            id: "auto-mining-rug",
+                            r#type: "checkbox",
+                            checked: miner.avoid_rug_risk,
+                            onchange: move |_| {
+                                let mut rig = MINING_RIG.write();
+                                let miner = rig.auto_miner_mut();
+                                miner.avoid_rug_risk = !miner.avoid_rug_risk;
+                                DO_SAVE.write().save = true;
+                            }
+                        }
+                        label { r#for: "auto-mining-rug", "Avoid rug risk" }
+                    }
+                    div {
+                        input {
+                            id: "auto-mining-blocks",
+                            r#type: "checkbox",
+                            checked: miner.prefer_blocks_left,
+                            onchange: move |_| {
+                                let mut rig = MINING_RIG.write();
+                                let miner = rig.auto_miner_mut();
+                                miner.prefer_blocks_left = !miner.prefer_blocks_left;
+                                DO_SAVE.write().save = true;
+                            }
+                        }
+                        label { r#for: "auto-mining-blocks", "Prefer coins with blocks left" }
+                    }
+                }
+            }
+        }
+
+        div { class: "flex flex-row", style: get_style_buttons(),
+            button {
+                class: "",
+                disabled: upgrade_disabled,
+                onclick: do_upgrade,
+                "Upgrade Auto-Mining"
+            }
+        }
+    }
+}
+
+#[component]
+pub fn RigMultiMiningTab(selected_tab: Signal<String>) -> Element {
+    let get_style = {
+        let selected_tab = selected_tab.clone();
+        move || {
+            if selected_tab() == "multi-mining" {
+                "display: block;padding: 10px;"
+            } else {
+                "display: none;padding: 10px;"
+            }
+        }
+    };
+
+    let get_style_buttons = {
+        let selected_tab = selected_tab.clone();
+        move || {
+            if selected_tab() == "multi-mining" {
+                "display: flex;justify-content: space-between;margin-top: 10px;"
+            } else {
+                "display: none;justify-content: space-between;"
+            }
+        }
+    };
+
+    let upgrade_available = {
+        if MARKET().bank.balance > SELECTION().get_upgrade_cost() && SELECTION().max_selectable < 10
+        {
+            false
+        } else {
+            true
+        }
+    };
+
+    let get_upgrade_cost = || {
+        if SELECTION().max_selectable >= 10 {
+            "Max Level".to_string()
+        } else {
+            format_comma_seperator(SELECTION().get_upgrade_cost(), 0)
+        }
+    };
+
+    rsx! {
+        div { class: "window", style: get_style(), role: "tabpanel",
+            div {
+                class: "flex flex-row",
+                style: "justify-content: space-between;",
+                div {
+                    h4 { "Multi Mining" }
+                    p { "Split the hashrate of your rig between multiple coins." }
+                    br {}
+                    p { "Level: {SELECTION().max_selectable}" }
+                    if SELECTION().max_selectable > 1 {
+                        br {}
+                        label { r#for: "hash-allocation", "Hash Allocation: " }
+                        select {
+                            id: "hash-allocation",
+                            class: "select",
+                            onchange: move |e| {
+                                if let Some(allocation) = HashAllocation::all()
+                                    .into_iter()
+                                    .find(|a| a.label() == e.value())
+                                {
+                                    SELECTION.write().set_allocation(allocation);
+                                    MARKET.write().set_profit_factor(&SELECTION());
+                                    DO_SAVE.write().save = true;
+                                }
+                            },
+                            for allocation in HashAllocation::all() {
+                                option {
+                                    value: "{allocation.label()}",
+                                    selected: allocation == SELECTION().get_allocation(),
+                                    "{allocation.label()}"
+                                }
+                            }
+                        }
+                    }
+                }
+                div {
+                    h4 { "Mining Upgrade" }
+                    br {}
+                    p { "Upgrade Cost:" }
+                    p { "${get_upgrade_cost()}" }
+                }
+            }
+        }
+
+        div { class: "flex flex", style: get_style_buttons(),
+            button {
+                class: "",
+                disabled: upgrade_available,
                 onclick: move |_| {
-                    if MARKET.write().bank.withdraw(SELECTION().get_upgrade_cost()) {
+                    if MARKET.write().spend(SELECTION().get_upgrade_cost(), LedgerKind::Upgrade, "Multi-Mining") {
                         SELECTION.write().increment_max_selectable();
                         DO_SAVE.write().save = true;
                     }
@@ -814,7 +1543,10 @@ pub fn RigRugProtectionTab(selected_tab: Signal<String>) -> Element {
     let do_rug_protection_upgrade = move |_| {
         let cost = rug_protection_cost;
 
-        if MARKET.write().bank.withdraw(cost) {
+        if MARKET
+            .write()
+            .spend(cost, LedgerKind::Upgrade, "Rug Protection")
+        {
             MINING_RIG.write().upgrade_rug_protection();
 
             let rug_protec_lvl = MINING_RIG().get_rug_protection_level();
@@ -841,7 +1573,7 @@ pub fn RigRugProtectionTab(selected_tab: Signal<String>) -> Element {
                     p {
                         "Rug Protection Level: {format_comma_seperator(MINING_RIG().get_rug_protection_level(), 2)}"
                     }
-                    p { "Multi-Mining Activated: {!MINING_RIG().get_global_share_cooldown()}" }
+                    p { "Multi-Mining Activated: {MINING_RIG().get_multi_mining_unlocked()}" }
                     p {
                         "Amount Rug Protected: {format_comma_seperator(MINING_RIG().get_rug_protection_amount() * 100.0, 2)}%"
                     }
@@ -866,13 +1598,101 @@ pub fn RigRugProtectionTab(selected_tab: Signal<String>) -> Element {
     }
 }
 
+#[component]
+pub fn RigResearchTab(selected_tab: Signal<String>) -> Element {
+    let get_style = {
+        move || {
+            if selected_tab() == "research" {
+                "display: block;padding: 10px;"
+            } else {
+                "display: none;padding: 10px;"
+            }
+        }
+    };
+
+    let get_style_buttons = {
+        move || {
+            if selected_tab() == "research" {
+                "display: flex;justify-content: space-between;margin-top: 10px;"
+            } else {
+                "display: none;justify-content: space-between;"
+            }
+        }
+    };
+
+    let due_diligence_level = MINING_RIG().get_due_diligence_level();
+    let due_diligence_cost = MINING_RIG().get_due_diligence_upgrade_cost();
+
+    let due_diligence_disabled = MARKET().bank.balance < due_diligence_cost
+        || due_diligence_level >= MAX_DUE_DILIGENCE_LEVEL;
+
+    let revealed = match due_diligence_level {
+        0 => "Social hype spikes",
+        1 => "Social hype, shrinking liquidity",
+        2 => "Social hype, liquidity, dev wallet outflows",
+        _ => "All warning signs and expected rug timing",
+    };
+
+    let do_due_diligence_upgrade = move |_| {
+        let cost = due_diligence_cost;
+
+        if MARKET
+            .write()
+            .spend(cost, LedgerKind::Upgrade, "Due Diligence")
+        {
+            MINING_RIG.write().upgrade_due_diligence();
+
+            let msg = format!(
+                "Due Diligence upgraded, new level {}",
+                MINING_RIG().get_due_diligence_level()
+            );
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+        DO_SAVE.write().save = true;
+    };
+
+    rsx! {
+        div { class: "window", style: get_style(), role: "tabpanel",
+            div {
+                class: "flex flex-row",
+                style: "justify-content: space-between;",
+                div {
+                    h4 { "Due Diligence" }
+                    p { "Research Level: {due_diligence_level}" }
+                    p { "Reveals: {revealed}" }
+                }
+                div {
+                    h4 { "Research Upgrade" }
+                    br {}
+                    if due_diligence_level < MAX_DUE_DILIGENCE_LEVEL {
+                        p { "Upgrade Cost: ${format_comma_seperator(due_diligence_cost, 2)}" }
+                    } else {
+                        p { "Fully researched" }
+                    }
+                }
+            }
+        }
+
+        div { class: "flex flex-row", style: get_style_buttons(),
+            button {
+                class: "",
+                disabled: due_diligence_disabled,
+                onclick: do_due_diligence_upgrade,
+                "Upgrade Due Diligence"
+            }
+        }
+    }
+}
+
 #[component]
 pub fn RigAsicTab(selected_tab: Signal<String>) -> Element {
     let upgrade_asic = {
         move |_| {
             let cost = MINING_RIG().get_asic_upgrade_cost();
 
-            if MARKET.write().bank.withdraw(cost) {
+            if MARKET.write().spend(cost, LedgerKind::Upgrade, "ASIC") {
                 MINING_RIG.write().upgrade_asic();
 
                 let asic_lvl = MINING_RIG().get_asic_level();
@@ -895,7 +1715,7 @@ pub fn RigAsicTab(selected_tab: Signal<String>) -> Element {
             let slots_available =
                 MINING_RIG().get_filled_asic_slots() < MINING_RIG().get_max_asic_slots();
 
-            if slots_available && MARKET.write().bank.withdraw(cost) {
+            if slots_available && MARKET.write().spend(cost, LedgerKind::Upgrade, "ASIC") {
                 MINING_RIG.write().upgrade_asic();
                 count += 1
             } else {
@@ -984,7 +1804,7 @@ pub fn RigGPUTab(selected_tab: Signal<String>) -> Element {
         move |_| {
             let cost = MINING_RIG().get_gpu_upgrade_cost();
 
-            if MARKET.write().bank.withdraw(cost) {
+            if MARKET.write().spend(cost, LedgerKind::Upgrade, "GPU") {
                 MINING_RIG.write().upgrade_gpu();
 
                 let gpu_lvl = MINING_RIG().get_gpu_level();
@@ -1006,7 +1826,7 @@ pub fn RigGPUTab(selected_tab: Signal<String>) -> Element {
             let slots_available =
                 MINING_RIG().get_filled_gpu_slots() < MINING_RIG().get_max_gpu_slots();
 
-            if slots_available && MARKET.write().bank.withdraw(cost) {
+            if slots_available && MARKET.write().spend(cost, LedgerKind::Upgrade, "GPU") {
                 MINING_RIG.write().upgrade_gpu();
 
                 count += 1;
@@ -1161,7 +1981,7 @@ pub fn RigCPUTab(selected_tab: Signal<String>) -> Element {
                 disabled: upgrade_available,
                 onclick: |_| {
                     let cost = MINING_RIG().get_cpu_upgrade_cost();
-                    if MARKET.write().bank.withdraw(cost) {
+                    if MARKET.write().spend(cost, LedgerKind::Upgrade, "CPU") {
                         MINING_RIG.write().upgrade_cpu();
                         let cpu_lvl = MINING_RIG().get_cpu_level();
                         let msg = format!("CPU upgrade successful, new level {cpu_lvl}");
@@ -1183,7 +2003,10 @@ pub fn RigDetailsTab(selected_tab: Signal<String>) -> Element {
         move |_| {
             let cost = MINING_RIG().get_auto_power_fill_upgrade_cost();
 
-            if MARKET.write().bank.withdraw(cost) {
+            if MARKET
+                .write()
+                .spend(cost, LedgerKind::Upgrade, "Auto-power fill")
+            {
                 MINING_RIG.write().upgrade_auto_power_fill();
 
                 let auto_fill_level = MINING_RIG().get_auto_power_fill_level();
@@ -1306,7 +2129,7 @@ pub fn RigDetailsTab(selected_tab: Signal<String>) -> Element {
                 disabled: can_upgrade_rig(),
                 onclick: |_| {
                     let cost = MINING_RIG().get_rig_upgrade_cost();
-                    if MARKET.write().bank.withdraw(cost) {
+                    if MARKET.write().spend(cost, LedgerKind::Upgrade, "Rig") {
                         MINING_RIG.write().upgrade();
                         let rig_lvl = MINING_RIG().get_level();
                         let msg = format!("Rig upgrade successful, new level {rig_lvl}");
@@ -1337,30 +2160,47 @@ pub fn RigMiningTab(selected_tab: Signal<String>, index: usize) -> Element {
         }
     };
 
-    let selected_coin_name = {
+    let selected_coin = {
         let sel = SELECTION().clone();
 
-        let selected_coin = sel.selection_by_index(index);
-        match selected_coin {
-            Some(selected) => selected.name.to_string(),
-            None => "Not Mining".to_string(),
+        match sel.selection_by_index(index) {
+            Some(selected) => MARKET().coin_by_id(selected.coin_id()).cloned(),
+            None => None,
         }
     };
 
-    let class_from_name = move |name: String| {
-        if name == "Not Mining" {
-            return "".to_string();
-        }
-        let mkt = MARKET.read();
-        let coin = mkt.coin_by_name(&name);
-        match coin {
-            Some(coin) => {
-                format!("selected-name-{}", coin.index)
-            }
-            None => "".to_string(),
-        }
+    let selected_coin_name = match &selected_coin {
+        Some(coin) => coin.name.clone(),
+        None => "Not Mining".to_string(),
+    };
+
+    let selected_coin_class = match &selected_coin {
+        Some(coin) => format!("selected-name-{}", coin.index),
+        None => "".to_string(),
+    };
+
+    let hash_rate = MINING_RIG().get_hash_rate();
+
+    let allocated_hash = match &selected_coin {
+        Some(coin) => SELECTION()
+            .hash_allocation(&MARKET().coins, hash_rate)
+            .iter()
+            .find(|(id, _)| *id == coin.id())
+            .map(|(_, hash)| *hash)
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    let hash_percent = if hash_rate > 0 {
+        allocated_hash as f64 / hash_rate as f64 * 100.0
+    } else {
+        0.0
     };
 
+    let custom_weight = SELECTION()
+        .selection_by_index(index)
+        .map(|s| SELECTION().weight_of(s));
+
     let get_style = {
         let selected_tab = selected_tab.clone();
         move || {
@@ -1408,12 +2248,51 @@ pub fn RigMiningTab(selected_tab: Signal<String>, index: usize) -> Element {
         div { class: "window", style: get_style(), role: "tabpanel",
             p {
                 style: "font-size: medium;float:right;",
-                class: "{class_from_name(selected_coin_name)} selected-name",
+                class: "{selected_coin_class} selected-name",
                 "{selected_coin_name}"
             }
-            h4 { "Share Progress" }
+            if let Some(coin) = &selected_coin {
+                p { class: "coin-hint",
+                    "Hash: {format_comma_seperator(hash_percent, 0)}% ({format_comma_seperator(allocated_hash, 0)} H/s) | ${format_comma_seperator(coin.profit_factor, 2)} / min"
+                }
+                if SELECTION().get_allocation() == HashAllocation::Custom {
+                    if let Some(weight) = custom_weight {
+                        div {
+                            class: "flex flex-row",
+                            style: "align-items: center;gap: 6px;margin-top: 4px;",
+                            label { r#for: "hash-weight-{index}", "Weight" }
+                            input {
+                                id: "hash-weight-{index}",
+                                r#type: "number",
+                                style: "width: 70px;",
+                                min: "0",
+                                max: "100",
+                                step: "5",
+                                value: "{weight:.0}",
+                                oninput: {
+                                    let id = coin.id();
+                                    move |e: Event<FormData>| {
+                                        if let Ok(value) = e.value().parse::<f64>() {
+                                            SELECTION.write().set_weight(id, value);
+                                            MARKET.write().set_profit_factor(&SELECTION());
+                                            DO_SAVE.write().save = true;
+                                        }
+                                    }
+                                }
+                            }
+                            span { class: "coin-hint",
+                                "= {format_comma_seperator(hash_percent, 0)}% of hash, weights are scaled to add up to 100%"
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(coin) = &selected_coin {
+                PoolDashboard { coin_id: coin.id(), index }
+            }
+            h4 { "Next Share" }
             ProgressBar { progress_id: format!("share-progress-{}", index), progress_message: "".to_string() }
-            h4 { "Block Progress" }
+            h4 { "Next Block" }
             ProgressBar { progress_id: format!("block-progress-{}", index), progress_message: "".to_string() }
             h4 { "Power Level" }
             ProgressBar {
@@ -2366,7 +3245,9 @@ pub fn NftMintModal(
 
         update_progess_bar("paint-progress", completed * 100.0).await;
 
-        MARKET.write().bank.deposit(nft.price);
+        MARKET
+            .write()
+            .earn(nft.price, LedgerKind::NftSale, &nft.name);
 
         clear_canvas(
             &mut paint_undo,
@@ -2720,6 +3601,25 @@ pub fn Header(ticks_per_second: Signal<TpsCounter>, selected_tab: Signal<String>
         }
     };
 
+    let tab_coin = {
+        let sel = SELECTION().clone();
+
+        let selected = match selected_tab().as_str() {
+            tab if tab.starts_with("mining") => {
+                let tab = tab.split("-").collect::<Vec<&str>>();
+                sel.selection_by_index(tab[1].parse::<usize>().unwrap())
+                    .cloned()
+            }
+            "details" => sel.get_first_selection().cloned(),
+            _ => None,
+        };
+
+        match selected {
+            Some(selected) => MARKET().coin_by_id(selected.coin_id()).cloned(),
+            None => None,
+        }
+    };
+
     let hash_rate = {
         let sel = SELECTION().clone();
         let coin_selections = sel.get_selected();
@@ -2729,7 +3629,7 @@ pub fn Header(ticks_per_second: Signal<TpsCounter>, selected_tab: Signal<String>
             .filter(|c| {
                 let mkt = MARKET().clone();
 
-                let coin = mkt.coin_by_name(&c.name);
+                let coin = mkt.coin_by_id(c.coin_id());
                 match coin {
                     Some(coin) => coin.active && coin.blocks < coin.max_blocks,
                     None => false,
@@ -2740,31 +3640,9 @@ pub fn Header(ticks_per_second: Signal<TpsCounter>, selected_tab: Signal<String>
 
         let rig_hash = MINING_RIG().get_hash_rate() / mineable;
 
-        let coin_hash = {
-            let selected_tab: String = match selected_tab().as_str() {
-                tab if tab.starts_with("mining") => {
-                    let tab = tab.split("-").collect::<Vec<&str>>();
-                    let sel = SELECTION().clone();
-                    let selected_coin = sel.selection_by_index(tab[1].parse::<usize>().unwrap());
-                    match selected_coin {
-                        Some(coin) => coin.name.to_owned(),
-                        None => "Not Mining".to_string(),
-                    }
-                }
-                "details" => match SELECTION().get_first_selection() {
-                    Some(coin) => coin.name.to_owned(),
-                    None => "Not Mining".to_string(),
-                },
-                _ => "Not Mining".to_string(),
-            };
-
-            let mkt = MARKET().clone();
-
-            let coin = mkt.coin_by_name(&selected_tab);
-            match coin {
-                Some(coin) => coin.get_effective_hash(rig_hash),
-                None => 0.0,
-            }
+        let coin_hash = match &tab_coin {
+            Some(coin) => coin.get_effective_hash(rig_hash),
+            None => 0.0,
         };
         format!(
             "{} | Effective {}",
@@ -2773,76 +3651,18 @@ pub fn Header(ticks_per_second: Signal<TpsCounter>, selected_tab: Signal<String>
         )
     };
 
-    let coin_balance = {
-        let selected_tab: String = match selected_tab().as_str() {
-            tab if tab.starts_with("mining") => {
-                let tab = tab.split("-").collect::<Vec<&str>>();
-                let sel = SELECTION().clone();
-                let selected_coin = sel.selection_by_index(tab[1].parse::<usize>().unwrap());
-                match selected_coin {
-                    Some(coin) => coin.name.to_owned(),
-                    None => "Not Mining".to_string(),
-                }
-            }
-            "details" => match SELECTION().get_first_selection() {
-                Some(coin) => coin.name.to_owned(),
-                None => "Not Mining".to_string(),
-            },
-            _ => "Not Mining".to_string(),
-        };
-
-        let mkt = MARKET().clone();
-
-        let coin = mkt.coin_by_name(&selected_tab);
-        match coin {
-            Some(coin) => coin.balance,
-            None => 0.0,
-        }
+    let coin_balance = match &tab_coin {
+        Some(coin) => coin.balance,
+        None => 0.0,
     };
 
-    let get_currently_mining = {
-        let selected_tab: String = match selected_tab().as_str() {
-            tab if tab.starts_with("mining") => {
-                let tab = tab.split("-").collect::<Vec<&str>>();
-                let sel = SELECTION().clone();
-                let selected_coin = sel.selection_by_index(tab[1].parse::<usize>().unwrap());
-                match selected_coin {
-                    Some(coin) => coin.name.to_owned(),
-                    None => "Not Mining".to_string(),
-                }
-            }
-            "details" => match SELECTION().get_first_selection() {
-                Some(coin) => coin.name.to_owned(),
-                None => "Not Mining".to_string(),
-            },
-            _ => "Not Mining".to_string(),
-        };
-
-        selected_tab
+    let get_currently_mining = match &tab_coin {
+        Some(coin) => coin.name.clone(),
+        None => "Not Mining".to_string(),
     };
 
     let get_coin_blocks = {
-        let selected_tab: String = match selected_tab().as_str() {
-            tab if tab.starts_with("mining") => {
-                let tab = tab.split("-").collect::<Vec<&str>>();
-                let sel = SELECTION().clone();
-                let selected_coin = sel.selection_by_index(tab[1].parse::<usize>().unwrap());
-                match selected_coin {
-                    Some(coin) => coin.name.to_owned(),
-                    None => "Not Mining".to_string(),
-                }
-            }
-            "details" => match SELECTION().get_first_selection() {
-                Some(coin) => coin.name.to_owned(),
-                None => "Not Mining".to_string(),
-            },
-
-            _ => "Not Mining".to_string(),
-        };
-
-        let mkt = MARKET().clone();
-
-        let coin = mkt.coin_by_name(&selected_tab);
+        let coin = tab_coin.as_ref();
         let blocks = match coin {
             Some(coin) => coin.blocks,
             None => 0,
@@ -2860,27 +3680,18 @@ pub fn Header(ticks_per_second: Signal<TpsCounter>, selected_tab: Signal<String>
         )
     };
 
-    let get_shares = {
-        let selected_tab: String = match selected_tab().as_str() {
-            tab if tab.starts_with("mining") => {
-                let tab = tab.split("-").collect::<Vec<&str>>();
-                let sel = SELECTION().clone();
-                let selected_coin = sel.selection_by_index(tab[1].parse::<usize>().unwrap());
-                match selected_coin {
-                    Some(coin) => coin.name.to_owned(),
-                    None => "Not Mining".to_string(),
-                }
-            }
-            "details" => match SELECTION().get_first_selection() {
-                Some(coin) => coin.name.to_owned(),
-                None => "Not Mining".to_string(),
-            },
-            _ => "Not Mining".to_string(),
-        };
-
-        let mkt = MARKET().clone();
+    let get_chain_stats = match &tab_coin {
+        Some(coin) => format!(
+            "Difficulty {} | Reward {} | Halvings {}",
+            format_comma_seperator(coin.get_difficulty(), 3),
+            format_comma_seperator(coin.get_block_reward(), 2),
+            coin.get_halvings()
+        ),
+        None => "No Chain".to_string(),
+    };
 
-        let coin = mkt.coin_by_name(&selected_tab);
+    let get_shares = {
+        let coin = tab_coin.as_ref();
         let shares = match coin {
             Some(coin) => coin.shares,
             None => 0.0,
@@ -2942,6 +3753,7 @@ pub fn Header(ticks_per_second: Signal<TpsCounter>, selected_tab: Signal<String>
                             p { "Coins: {format_comma_seperator(coin_balance, 5)}" }
                             p { "Shares: {get_shares}" }
                             p { "Blocks: {get_coin_blocks}" }
+                            p { "Chain: {get_chain_stats}" }
                             p { "Hash Rate: {hash_rate}" }
                         }
                         div {
@@ -3024,63 +3836,69 @@ pub fn CommandLine() -> Element {
 }
 
 #[component]
-pub fn WelcomeModal() -> Element {
-    let close_modal = {
-        move |_| {
-            WELCOME_MODAL.write().show = false;
+pub fn NewsTicker() -> Element {
+    let news = MARKET().news();
+    let headlines = news.latest();
+
+    let impact_style = |v: f64| {
+        if v < 0.0 {
+            "padding: 3px;color: #a00;"
+        } else {
+            "padding: 3px;color: #070;"
         }
     };
 
     rsx! {
-        if WELCOME_MODAL().show {
-            // Backdrop
-            div { class: "backdrop" }
-            // Modal content
-            div {
-                class: "window modal pauseModal",
-                style: "max-width: 350px;min-width:225px;",
+        div { class: "items-center justify-center container",
+            div { class: "aspect-w-1 aspect-h-1 overflow-hidden window h-fit",
                 div { class: "title-bar",
-                    div { class: "title-bar-text", "Welcome" }
+                    div { class: "title-bar-text", "News" }
                     div { class: "title-bar-controls",
                         button {
                             class: "close",
                             aria_label: "Close",
-                            onclick: close_modal,
+                            onclick: |_| {
+                                info!("Closing window");
+                            },
                             ""
                         }
                     }
                 }
-                div { class: "window-body ",
-                    div { class: "p-6  mx-auto",
-                        h3 { "Welcome to HashQuest" }
-
-                        br {}
-
-                        p { "HashQuest is a cryptocurrency mining simulation game." }
-                        p { "You will start with a basic click powered mining rig." }
-                        p { "You can upgrade your rig with new gear to mine more cois faster." }
-                        p { "Before long you will be the Hash Rate Lord!" }
-
-                        br {}
-
-                        p { "But watch out for rug pulls!" }
-                        p {
-                            "Rug pulls can happen at any time, and any balance of that coin is wiped out."
+                div { class: "window-body",
+                    div {
+                        class: "sunken-panel",
+                        style: "height: 150px; overflow: auto;",
+                        table { class: "interactive w-full noselect",
+                            thead {
+                                tr {
+                                    th { "Day" }
+                                    th { "Headline" }
+                                    th { "" }
+                                }
+                            }
+                            tbody {
+                                for event in headlines.iter() {
+                                    tr {
+                                        td { style: "padding: 3px;", "{event.day}" }
+                                        td { style: "padding: 3px;", "{event.headline}" }
+                                        td { style: impact_style(event.impact),
+                                            if event.impact < 0.0 {
+                                                "▼"
+                                            } else {
+                                                "▲"
+                                            }
+                                        }
+                                    }
+                                }
+                            }
                         }
-                        p { "The higher a coins age, the higher the chance of a rug pull." }
-
-                        br {}
-
-                        p { "For more information, click the ? button in the title card" }
-
-                        h4 { "Good luck!" }
                     }
-
-                    button {
-                        class: "",
-                        style: "margin-top: 10px;",
-                        onclick: close_modal,
-                        "Start Game"
+                    div { class: "status-bar", style: "margin-top:10px;",
+                        p {
+                            class: "status-bar-field p-1 font-mono p-2",
+                            style: "padding:4px;",
+                            "Active Stories: {news.active.len()}"
+                        }
                     }
                 }
             }
@@ -3089,81 +3907,105 @@ pub fn WelcomeModal() -> Element {
 }
 
 #[component]
-pub fn HelpModal() -> Element {
-    let close_modal = {
-        move |_| {
-            HELP_MODAL.write().show = false;
+pub fn PortfolioDashboard() -> Element {
+    let history = MARKET().net_worth();
+    let current = MARKET().net_worth_snapshot(GAME_TIME().day, NFT_STUDIO().value());
+    let total = current.total();
+    let daily_change = history.daily_change();
+    let allocation = MARKET().allocation();
+
+    let worth_series = vec![history
+        .snapshots
+        .iter()
+        .map(|s| s.total() as f32)
+        .collect::<Vec<f32>>()];
+    let worth_labels = history
+        .snapshots
+        .iter()
+        .map(|s| format!("D{}", s.day))
+        .collect::<Vec<String>>();
+
+    let share = move |value: f64| {
+        if total <= 0.0 {
+            0.0
+        } else {
+            value / total * 100.0
         }
     };
 
+    let change_style = if daily_change < 0.0 {
+        "color: #a00;"
+    } else {
+        "color: #070;"
+    };
+
     rsx! {
-        if HELP_MODAL().show {
-            // Backdrop
-            div { class: "backdrop" }
-            // Modal content
+        div { class: "window", role: "tabpanel", style: "padding: 10px;",
+            h4 { "Net Worth: ${format_comma_seperator(total, 2)}" }
+            p { style: change_style,
+                "Since yesterday: ${format_comma_seperator(daily_change, 2)}"
+            }
+            if worth_labels.len() > 1 {
+                LineChart {
+                    padding_top: 20,
+                    padding_left: 70,
+                    padding_right: 20,
+                    padding_bottom: 30,
+                    height: "150px",
+                    series: worth_series,
+                    labels: worth_labels,
+                    label_interpolation: (|v| format!("${}", format_compact(v as f64))) as fn(f32) -> String,
+                    show_dots: false,
+                    show_grid: false,
+                    line_width: "0.5%",
+                    max_ticks: 6
+                }
+            } else {
+                p { class: "coin-hint", "The trend fills in as days pass." }
+            }
             div {
-                class: "window modal pauseModal",
-                style: "max-width: 350px;min-width:225px;",
-                div { class: "title-bar",
-                    div { class: "title-bar-text", "Help" }
-                    div { class: "title-bar-controls",
-                        button {
-                            class: "close",
-                            aria_label: "Close",
-                            onclick: close_modal,
-                            ""
+                class: "sunken-panel",
+                style: "height: 150px; overflow: auto;margin-top: 10px;",
+                table { class: "interactive w-full noselect",
+                    thead {
+                        tr {
+                            th { "Holding" }
+                            th { "Value" }
+                            th { "Share" }
                         }
                     }
-                }
-                div { class: "window-body ",
-                    div { class: "p-6  mx-auto",
-                        h3 { "How to Play HashQuest" }
-
-                        br {}
-
-                        h4 { "Getting Started" }
-
-                        p { "To start playing HashQuest, you will need to mine a cryptocurrency." }
-                        p { "To mine a cryptocurrency, you will need to select a coin to mine." }
-                        p {
-                            "Once you have selected a coin to mine, mining will begin so long as there is power available."
-                        }
-
-                        br {}
-
-                        p {
-                            "To power your rig, you can use the 'Click Power' button to charge your power level."
-                        }
-                        p {
-                            "You can also use the 'Fill Power' button to fill your power level to 100% for a fee."
-                        }
-
-                        br {}
-
-                        p {
-                            "You can sell the coins that you mine for money that is used to upgrade your mining rig."
-                        }
-                        p {
-                            "Upgrades do things like increase hashrate, lower cooldowns, rug pull protection, and automatically refill your power."
+                    tbody {
+                        for (label, value) in current.parts() {
+                            if value > 0.0 {
+                                tr {
+                                    td { style: "padding: 3px;", "{label}" }
+                                    td { style: "padding: 3px;",
+                                        "${format_comma_seperator(value, 2)}"
+                                    }
+                                    td { style: "padding: 3px;",
+                                        "{format_comma_seperator(share(value), 1)}%"
+                                    }
+                                }
+                            }
                         }
-
-                        br {}
-
-                        p {
-                            "Rug pulls can happen at any time. Any balance of a rug pulled coin is lost, so make sure to sell before a rug."
+                        for (coin, value) in allocation.iter() {
+                            tr {
+                                td { style: "padding: 3px;padding-left: 15px;",
+                                    div {
+                                        class: "flex flex-row",
+                                        style: "align-items: center;gap: 4px;",
+                                        CoinIcon { coin: coin.clone() }
+                                        "{coin.name}"
+                                    }
+                                }
+                                td { style: "padding: 3px;",
+                                    "${format_comma_seperator(*value, 2)}"
+                                }
+                                td { style: "padding: 3px;",
+                                    "{format_comma_seperator(share(*value), 1)}%"
+                                }
+                            }
                         }
-                        p { "The higher a coins age, the higher the chance of a rug pull." }
-
-                        br {}
-
-                        h4 { "Good Luck!" }
-                    }
-
-                    button {
-                        class: "",
-                        style: "margin-top: 10px;",
-                        onclick: close_modal,
-                        "Close"
                     }
                 }
             }
@@ -3172,228 +4014,274 @@ pub fn HelpModal() -> Element {
 }
 
 #[component]
-pub fn Modal(confirm_modal: Signal<ConfirmModal>) -> Element {
-    let close_modal = {
-        move |_| {
-            IS_PAUSED.write().toggle();
-            DO_SAVE.write().save = true;
-            if let Some(mut galaxy_save_details) = GALAXY_SAVE_DETAILS() {
-                if galaxy_save_details.active && galaxy_save_details.slot.is_some() {
-                    galaxy_save_details.force_save = true;
-                    *GALAXY_SAVE_DETAILS.write() = Some(galaxy_save_details);
-                }
-            }
-        }
-    };
+pub fn Notifications() -> Element {
+    let alerts = MARKET().alerts();
+    let unread = alerts.unread();
 
-    let new_game = {
-        move |_| {
-            use_future(move || async move {
-                let mut confirm_modal = confirm_modal.clone();
+    let watched: Vec<CryptoCoin> = alerts
+        .watchlist
+        .iter()
+        .filter_map(|id| MARKET().coin_by_id(*id).cloned())
+        .collect();
 
-                let msg = "Are you sure you want to start a new game?".to_string();
+    let notifications: Vec<alerts::Notification> =
+        alerts.notifications.iter().rev().cloned().collect();
 
-                confirm_modal.write().msg = msg;
-                confirm_modal.write().show = true;
+    let toggle_browser = move |_| {
+        let enable = !MARKET().alerts().browser_notifications;
 
-                let confirm = loop {
-                    let conf = confirm_modal().confirm;
-                    match conf {
-                        Some(conf) => {
-                            confirm_modal.write().confirm = None;
-                            break conf;
-                        }
-                        None => TimeoutFuture::new(100).await,
-                    }
-                };
+        spawn_local(async move {
+            let granted = !enable || request_notification_permission().await;
+            MARKET.write().alerts_mut().browser_notifications = enable && granted;
 
-                if confirm {
-                    clear_game_state().await;
-                    clear_paint_undo().await;
+            if enable && !granted {
+                command_line_output("Browser notifications were not allowed.").await;
+            }
 
-                    let galaxy_host = get_galaxy_host().await;
+            DO_SAVE.write().save = true;
+        });
+    };
 
-                    match galaxy_host {
-                        Ok(galaxy_host) => match galaxy_host {
-                            Some(galaxy_host) => {
-                                if galaxy_host.galaxy && galaxy_host.logged_in {
-                                    if let Some(galaxy_save_details) = GALAXY_SAVE_DETAILS() {
-                                        if galaxy_save_details.active
-                                            && galaxy_save_details.slot.is_some()
-                                        {
-                                            info!("Deleting cloud save");
-
-                                            let save_slot = galaxy_save_details.slot.unwrap();
-                                            delete_cloud_save(save_slot).await;
-                                        }
-                                    };
-                                }
-                            }
-                            None => {}
-                        },
-                        Err(_) => {}
-                    }
-
-                    let win = window();
-
-                    win.location().reload().unwrap();
-                }
-            });
-        }
-    };
-
-    let show_help_modal = {
-        move || {
-            HELP_MODAL.write().show = true;
-        }
-    };
-
-    let show_import_export_modal = {
-        move || {
-            IMPORT_EXPORT_MODAL.write().show = true;
-        }
-    };
-
-    let auto_save_time_opts: Vec<u64> = Vec::from([5, 10, 15, 20, 30, 60, 90, 120, 180, 240, 300]);
-    let mut selected_time: Signal<u64> = use_signal(|| 30);
+    let change_style = |v: f64| {
+        if v < 0.0 {
+            "padding: 3px;color: #a00;"
+        } else {
+            "padding: 3px;color: #070;"
+        }
+    };
 
     rsx! {
-        if IS_PAUSED().paused {
-            // Backdrop
-            div { class: "backdrop" }
-            // Modal content
-            div { class: "window modal pauseModal",
+        div { class: "items-center justify-center container",
+            div { class: "aspect-w-1 aspect-h-1 overflow-hidden window h-fit",
                 div { class: "title-bar",
-                    div { class: "title-bar-text", "Paused" }
-                    div { class: "title-bar-controls",
-                        button {
-                            class: "close",
-                            aria_label: "Help",
-                            onclick: move |_| {
-                                show_help_modal();
-                            },
-                            ""
+                    div { class: "title-bar-text",
+                        if unread > 0 {
+                            "Notifications ({unread})"
+                        } else {
+                            "Notifications"
                         }
+                    }
+                    div { class: "title-bar-controls",
                         button {
                             class: "close",
                             aria_label: "Close",
-                            onclick: close_modal,
+                            onclick: |_| {
+                                info!("Closing window");
+                            },
                             ""
                         }
                     }
                 }
-                div { class: "window-body ",
+                div { class: "window-body",
                     div {
-                        class: "window",
-                        style: "margin-bottom: 10px;padding: 10px;text-align: center;min-width: 225px;",
-                        h3 { "Game Paused" }
-
-                        br {}
-
-                        h4 { "Hint" }
-                        p { "Add to your home screen to play offline." }
-
-                        br {}
-
-                        p {
-                            span { "Now available on " }
-                            a {
-                                href: "https://play.google.com/store/apps/details?id=lol.hashquest.twa",
-                                target: "_blank",
-                                style: "text-decoration:underline;",
-                                "Google Play"
+                        class: "sunken-panel",
+                        style: "height: 100px; overflow: auto;",
+                        table { class: "interactive w-full noselect",
+                            thead {
+                                tr {
+                                    th { "Watching" }
+                                    th { "Price" }
+                                    th { "24h" }
+                                    th { "Alerts" }
+                                }
                             }
-                        }
-
-                        br {}
-
-                        if GALAXY_SAVE_DETAILS().is_some() {
-                            div { class: "flex flex-col",
-                                div {
-                                    input {
-                                        id: "cloud-save",
-                                        class: "",
-                                        style: "",
-                                        r#type: "checkbox",
-                                        checked: GALAXY_SAVE_DETAILS().as_ref().unwrap().active,
-                                        onclick: move |_| {
-                                            let toggle_autosave = toggle_autosave.clone();
-                                            async move {
-                                                toggle_autosave().await;
+                            tbody {
+                                for coin in watched.iter() {
+                                    tr {
+                                        onclick: {
+                                            let coin = coin.clone();
+                                            move |_| {
+                                                BUY_MODAL.write().coin = Some(coin.clone());
+                                                BUY_MODAL.write().show = true;
                                             }
                                         },
-                                        prevent_default: "onclick"
-                                    }
-                                    label { class: "", r#for: "cloud-save",
-                                        "Autosave to Galaxy.click Cloud"
+                                        td { style: "padding: 3px;", "{coin.name}" }
+                                        td { style: "padding: 3px;",
+                                            "${format_comma_seperator(coin.current_price, 2)}"
+                                        }
+                                        td { style: change_style(daily_change(coin)),
+                                            "{format_comma_seperator(daily_change(coin) * 100.0, 1)}%"
+                                        }
+                                        td { style: "padding: 3px;", "{alerts.alerts_for(coin.id()).len()}" }
                                     }
                                 }
-                                if GALAXY_SAVE_DETAILS().as_ref().unwrap().active {
-                                    div {
-                                        style: "margin-top: 10px;",
-                                        class: "flex flex-col",
-                                        label { r#for: "auto-save-time",
-                                            "Auto Cloud Save Time (seconds): "
-                                        }
-                                        select {
-                                            id: "auto-save-time",
-                                            value: "{GALAXY_SAVE_DETAILS().unwrap().save_interval}",
-                                            oninput: move |event| {
-                                                if let Ok(value) = event.value().parse::<u64>() {
-                                                    selected_time.set(value);
-                                                    if let Some(mut galaxy_save_details) = GALAXY_SAVE_DETAILS() {
-                                                        if galaxy_save_details.active && galaxy_save_details.slot.is_some() {
-                                                            let save_interval = selected_time();
-                                                            galaxy_save_details.save_interval = save_interval;
-                                                            galaxy_save_details.force_save = true;
-                                                            *GALAXY_SAVE_DETAILS.write() = Some(galaxy_save_details);
-                                                            DO_SAVE.write().save = true;
-                                                        }
-                                                    }
-                                                }
-                                            },
-                                            // Generate the dropdown options
-                                            for time in auto_save_time_opts.iter() {
-                                                option {
-                                                    value: "{time}",
-                                                    selected: *time == selected_time(),
-                                                    "{time}"
-                                                }
-                                            }
+                            }
+                        }
+                    }
+                    div {
+                        class: "sunken-panel",
+                        style: "height: 120px; overflow: auto;margin-top: 10px;",
+                        table { class: "interactive w-full noselect",
+                            thead {
+                                tr {
+                                    th { "Day" }
+                                    th { "Alert" }
+                                }
+                            }
+                            tbody {
+                                for notification in notifications.iter() {
+                                    tr {
+                                        td { style: "padding: 3px;", "{notification.day}" }
+                                        td {
+                                            style: if notification.read { "padding: 3px;" } else { "padding: 3px;font-weight: bold;" },
+                                            "{notification.message}"
                                         }
                                     }
                                 }
                             }
                         }
-
+                    }
+                    div {
+                        class: "flex flex-row",
+                        style: "justify-content: space-between;align-items: center;margin-top: 10px;",
+                        div { class: "field-row",
+                            input {
+                                id: "browser-notifications",
+                                r#type: "checkbox",
+                                checked: alerts.browser_notifications,
+                                onchange: toggle_browser
+                            }
+                            label { r#for: "browser-notifications", "Browser notifications" }
+                        }
                         div {
-                            class: "flex flex-row",
-                            style: "justify-content: space-between;",
                             button {
-                                class: "",
-                                style: "margin-top: 10px;",
+                                disabled: unread == 0,
                                 onclick: move |_| {
-                                    show_help_modal();
+                                    MARKET.write().alerts_mut().mark_all_read();
+                                    DO_SAVE.write().save = true;
                                 },
-                                "Help"
+                                "Mark Read"
                             }
                             button {
-                                class: "",
-                                style: "margin-top: 10px;",
                                 onclick: move |_| {
-                                    show_import_export_modal();
+                                    MARKET.write().alerts_mut().clear_notifications();
+                                    DO_SAVE.write().save = true;
                                 },
-                                "Import/Export"
+                                "Clear"
                             }
                         }
+                    }
+                }
+            }
+        }
+    }
+}
 
-                        p { "Click Resume to continue your game." }
+#[component]
+pub fn IcoCalendar() -> Element {
+    let offerings = MARKET().ico().offerings;
+    let day = GAME_TIME().day;
+
+    let do_buy_presale = move |id: CoinId, amount: f64| {
+        let bought = MARKET.write().buy_presale(id, amount);
+
+        let name = match MARKET().ico().offering(id) {
+            Some(offering) => offering.coin.name.clone(),
+            None => "ICO".to_string(),
+        };
+
+        let msg = if bought {
+            format!("Bought {amount} {name} in presale.")
+        } else {
+            format!("Presale of {amount} {name} failed.")
+        };
+        spawn_local(async move {
+            command_line_output(&msg).await;
+        });
+
+        DO_SAVE.write().save = true;
+    };
+
+    let can_buy_presale = |offering: &IcoOffering, amount: f64| {
+        amount > 0.0
+            && amount <= offering.remaining_allocation()
+            && MARKET().bank.balance >= amount * offering.presale_price
+    };
+
+    rsx! {
+        div { class: "items-center justify-center container",
+            div { class: "aspect-w-1 aspect-h-1 overflow-hidden window h-fit",
+                div { class: "title-bar",
+                    div { class: "title-bar-text", "Upcoming ICOs" }
+                    div { class: "title-bar-controls",
+                        button {
+                            class: "close",
+                            aria_label: "Close",
+                            onclick: |_| {
+                                info!("Closing window");
+                            },
+                            ""
+                        }
                     }
+                }
+                div { class: "window-body",
                     div {
-                        class: "flex flex-row",
-                        style: "justify-content: space-between;",
-                        button { class: "", onclick: close_modal, "Resume" }
-                        button { class: "", onclick: new_game, "New Game" }
+                        class: "sunken-panel",
+                        style: "height: 150px; overflow: auto;",
+                        table { class: "interactive w-full noselect",
+                            thead {
+                                tr {
+                                    th { "Coin" }
+                                    th { "Presale" }
+                                    th { "Cap Left" }
+                                    th { "Lists" }
+                                    th { "Buy" }
+                                }
+                            }
+                            tbody {
+                                for offering in offerings.iter() {
+                                    tr {
+                                        td { style: "padding: 3px;",
+                                            div {
+                                                class: "flex flex-row",
+                                                style: "align-items: center;gap: 4px;",
+                                                CoinIcon { coin: offering.coin.clone() }
+                                                "{offering.coin.name} ({offering.coin.get_ticker()})"
+                                            }
+                                            p { class: "coin-hint", "{offering.coin.hint()}" }
+                                            if offering.purchased > 0.0 {
+                                                p { class: "coin-hint",
+                                                    "Bought {format_comma_seperator(offering.purchased, 2)}"
+                                                }
+                                            }
+                                        }
+                                        td { style: "padding: 3px;",
+                                            "${format_comma_seperator(offering.presale_price, 2)}"
+                                        }
+                                        td { style: "padding: 3px;",
+                                            "{format_comma_seperator(offering.remaining_allocation(), 0)}"
+                                        }
+                                        td { style: "padding: 3px;",
+                                            if offering.is_ready(day) {
+                                                "Next slot"
+                                            } else {
+                                                "Day {offering.listing_day}"
+                                            }
+                                        }
+                                        td { style: "padding: 3px;",
+                                            for amount in [10.0, 100.0] {
+                                                button {
+                                                    class: "sell-btn",
+                                                    disabled: !can_buy_presale(offering, amount),
+                                                    onclick: {
+                                                        let id = offering.id();
+                                                        move |_| do_buy_presale(id, amount)
+                                                    },
+                                                    "+{amount}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                    div { class: "status-bar", style: "margin-top:10px;",
+                        p {
+                            class: "status-bar-field p-1 font-mono p-2",
+                            style: "padding:4px;",
+                            "Presale coins are credited when the coin lists"
+                        }
                     }
                 }
             }
@@ -3402,170 +4290,88 @@ pub fn Modal(confirm_modal: Signal<ConfirmModal>) -> Element {
 }
 
 #[component]
-pub fn ImportExportModal(
-    series: Signal<Vec<Vec<f64>>>,
-    series_labels: Signal<Vec<String>>,
-    labels: Signal<Vec<String>>,
-) -> Element {
+pub fn WelcomeModal() -> Element {
     let close_modal = {
         move |_| {
-            IMPORT_EXPORT_MODAL.write().show = false;
+            WELCOME_MODAL.write().show = false;
         }
     };
 
-    let clear_textarea = {
-        move || {
-            let window = window();
-            let document = window.document().expect("document not found");
-            let textarea = document
-                .get_element_by_id("import-export-textarea")
-                .expect("textarea not found")
-                .dyn_into::<web_sys::HtmlTextAreaElement>()
-                .expect("textarea not found");
-            textarea.set_value("");
-        }
-    };
-
-    let export_game = {
-        move || {
-            use_future(move || async move {
-                let game_state_res = get_game_state().await;
-
-                let game_state_opt = match game_state_res {
-                    Ok(game_state) => game_state,
-                    Err(_) => None,
-                };
-
-                let game_state = match game_state_opt {
-                    Some(game_state) => game_state,
-                    None => {
-                        let _ = window().alert_with_message("Failed to export game data.");
-                        return;
-                    }
-                };
-
-                let game_state = export_game_state(&game_state).await;
-
-                match game_state {
-                    Some(game_state) => {
-                        let window = window();
-                        let clipboard = window.navigator().clipboard();
-
-                        let result: js_sys::Promise = clipboard.write_text(&game_state);
-                        let future = JsFuture::from(result);
-
-                        match future.await {
-                            Ok(_) => {
-                                spawn_local(async move {
-                                    command_line_output("Game data copied to clipboard.").await;
-                                });
-
-                                let document = window.document().expect("document not found");
-                                let export_button = document
-                                    .get_element_by_id("export-button")
-                                    .expect("export button not found")
-                                    .dyn_into::<web_sys::HtmlButtonElement>()
-                                    .expect("export button not found");
-
-                                let _ = window.alert_with_message("Game data copied to clipboard.\nUse this data to import your game later.\n\nKeep it safe!");
-
-                                export_button.set_disabled(true);
-                                export_button.set_inner_text("Copied");
-
-                                TimeoutFuture::new(2000).await;
-
-                                export_button.set_disabled(false);
-                                export_button.set_inner_text("Export");
-                            }
-                            Err(_) => {
-                                let _ = window
-                                    .alert_with_message("Failed to copy game data to clipboard.");
-                            }
+    rsx! {
+        if WELCOME_MODAL().show {
+            // Backdrop
+            div { class: "backdrop" }
+            // Modal content
+            div {
+                class: "window modal pauseModal",
+                style: "max-width: 350px;min-width:225px;",
+                div { class: "title-bar",
+                    div { class: "title-bar-text", "Welcome" }
+                    div { class: "title-bar-controls",
+                        button {
+                            class: "close",
+                            aria_label: "Close",
+                            onclick: close_modal,
+                            ""
                         }
                     }
-                    None => {}
                 }
-            })
-        }
-    };
-
-    let import_game_data = {
-        move || {
-            let win = window();
-            let document = win.document().expect("document not found");
-            let textarea = document
-                .get_element_by_id("import-export-textarea")
-                .expect("textarea not found")
-                .dyn_into::<web_sys::HtmlTextAreaElement>()
-                .expect("textarea not found");
-
-            let import_button = document
-                .get_element_by_id("import-button")
-                .expect("import button not found")
-                .dyn_into::<web_sys::HtmlButtonElement>()
-                .expect("import button not found");
-
-            import_button.set_disabled(true);
-            import_button.set_inner_text("Importing...");
-
-            let game_data = textarea.value();
-            let game_data = game_data.trim().to_string();
-            let game_clone = game_data.clone();
-
-            if game_data.is_empty() {
-                spawn_local(async move {
-                    command_line_output("No Game data to import.").await;
-                });
+                div { class: "window-body ",
+                    div { class: "p-6  mx-auto",
+                        h3 { "Welcome to HashQuest" }
 
-                import_button.set_disabled(false);
-                import_button.set_inner_text("Import");
+                        br {}
 
-                return;
-            }
+                        p { "HashQuest is a cryptocurrency mining simulation game." }
+                        p { "You will start with a basic click powered mining rig." }
+                        p { "You can upgrade your rig with new gear to mine more cois faster." }
+                        p { "Before long you will be the Hash Rate Lord!" }
 
-            use_future(move || {
-                let game_clone = game_clone.clone();
-                async move {
-                    let res = load_game_from_string(game_clone).await;
-                    let win = window();
+                        br {}
 
-                    match res {
-                        true => {
-                            let _ = win.alert_with_message(
-                                "Game data imported successfully!\nThe game will now reload.",
-                            );
-                            win.location().reload().unwrap();
+                        p { "But watch out for rug pulls!" }
+                        p {
+                            "Rug pulls can happen at any time, and any balance of that coin is wiped out."
                         }
-                        false => {
-                            let _ = win.alert_with_message(
-                                "Failed to import game data.\nPlease check the data and try again.",
-                            );
+                        p { "The higher a coins age, the higher the chance of a rug pull." }
 
-                            let document = win.document().expect("document not found");
+                        br {}
 
-                            let import_button = document
-                                .get_element_by_id("import-button")
-                                .expect("import button not found")
-                                .dyn_into::<web_sys::HtmlButtonElement>()
-                                .expect("import button not found");
+                        p { "For more information, click the ? button in the title card" }
 
-                            import_button.set_disabled(false);
-                            import_button.set_inner_text("Import");
-                        }
+                        h4 { "Good luck!" }
+                    }
+
+                    button {
+                        class: "",
+                        style: "margin-top: 10px;",
+                        onclick: close_modal,
+                        "Start Game"
                     }
                 }
-            });
+            }
+        }
+    }
+}
+
+#[component]
+pub fn HelpModal() -> Element {
+    let close_modal = {
+        move |_| {
+            HELP_MODAL.write().show = false;
         }
     };
 
     rsx! {
-        if IMPORT_EXPORT_MODAL().show {
+        if HELP_MODAL().show {
             // Backdrop
             div { class: "backdrop" }
             // Modal content
-            div { class: "window modal pauseModal",
+            div {
+                class: "window modal pauseModal",
+                style: "max-width: 350px;min-width:225px;",
                 div { class: "title-bar",
-                    div { class: "title-bar-text", "Import/Export" }
+                    div { class: "title-bar-text", "Help" }
                     div { class: "title-bar-controls",
                         button {
                             class: "close",
@@ -3576,82 +4382,114 @@ pub fn ImportExportModal(
                     }
                 }
                 div { class: "window-body ",
-                    div {
-                        class: "window",
-                        style: "margin-bottom: 10px;padding: 10px;text-align: center;min-width: 225px;",
-                        h3 { "Import/Export Game" }
+                    div { class: "p-6  mx-auto",
+                        h3 { "How to Play HashQuest" }
 
                         br {}
 
-                        p { style: "font-size: small;",
-                            "To import a game, paste your game data below."
-                        }
-                        textarea {
-                            id: "import-export-textarea",
-                            class: "w-full",
-                            style: "font-family: 'Consolas', 'Courier New', Courier, monospace;padding: 10px;line-height: 1.75;",
-                            cols: "30",
-                            resize: "none"
-                        }
+                        h4 { "Getting Started" }
 
-                        div {
-                            class: "flex flex-row",
-                            style: "justify-content: space-between;",
-                            button {
-                                id: "import-button",
-                                class: "",
-                                style: "margin-top: 10px;",
-                                onclick: move |_| {
-                                    import_game_data();
-                                },
-                                "Import"
-                            }
-                            button {
-                                class: "",
-                                style: "margin-top: 10px;",
-                                onclick: move |_| {
-                                    clear_textarea();
-                                },
-                                "Clear"
-                            }
+                        p { "To start playing HashQuest, you will need to mine a cryptocurrency." }
+                        p { "To mine a cryptocurrency, you will need to select a coin to mine." }
+                        p {
+                            "Once you have selected a coin to mine, mining will begin so long as there is power available."
                         }
 
                         br {}
 
-                        p { style: "font-size: small;", "To export a game, click the button below." }
-                        p { style: "font-size: small;",
-                            "Save the copied data in a safe place to import your game later."
+                        p {
+                            "To power your rig, you can use the 'Click Power' button to charge your power level."
+                        }
+                        p {
+                            "You can also use the 'Fill Power' button to fill your power level to 100% for a fee."
                         }
 
-                        div {
-                            class: "flex flex-row",
-                            style: "justify-content: space-between;",
-                            button {
-                                id: "export-button",
-                                class: "",
-                                style: "margin-top: 10px;",
-                                onclick: move |_| {
-                                    export_game();
-                                },
-                                "Export"
-                            }
+                        br {}
+
+                        p {
+                            "You can sell the coins that you mine for money that is used to upgrade your mining rig."
                         }
-                        p { style: "font-size: small;margin-top: 10px;",
-                            span { "We recommend using " }
-                            span {
-                                a {
-                                    href: "https://e2epaste.xyz",
-                                    target: "_blank",
-                                    "e2epaste.xyz"
-                                }
-                            }
-                            span { " to securly transfer your game data to a different device." }
+                        p {
+                            "Upgrades do things like increase hashrate, lower cooldowns, rug pull protection, and automatically refill your power."
+                        }
+
+                        br {}
+
+                        p {
+                            "Rug pulls can happen to any coin. Any balance of a rug pulled coin is lost, so make sure to sell before a rug."
+                        }
+                        p { "The higher a coins age, the higher the chance of a rug pull." }
+                        p {
+                            "Each coin shows a profile hint. Pegged coins are calm and long lived, hyped coins pay more but may not last."
+                        }
+                        p {
+                            "Coins about to be rug pulled show warning signs a few days ahead. Research due diligence to spot the subtler ones."
+                        }
+                        p {
+                            "The market window also lets you short a coin or take a leveraged long. Margin positions pay interest every day and are liquidated if the price moves too far against them. Shorts on a rug pulled coin pay out in full."
+                        }
+                        p {
+                            "Pick a coin under the market chart to focus on it and switch on moving averages, Bollinger bands, RSI and volume bars."
+                        }
+                        p {
+                            "A focused coin can also be zoomed out past the last day. Older prices are kept as daily candles for four weeks and as weekly candles after that, so the lifetime view covers the coin's whole history."
+                        }
+                        p {
+                            "Auto-Mining, unlocked at rig level 15, picks the coins with the best profit for you and switches your rig over as the market moves. Higher levels re-rank more often and fill more of your multi mining slots. It can also steer clear of coins with rug warning signs and favour coins with more blocks left."
+                        }
+                        p {
+                            "Each coin can be mined solo or through a pool, picked from the mining tab. Solo mining has no fee and keeps the block bonus, but you are only paid when you find a block yourself. PPS pools pay a steady amount for every share for a higher fee, while PPLNS pools pay your recent shares whenever the pool finds a block, so pool luck decides when you get paid."
+                        }
+                        p {
+                            "Your rig's network latency means some shares arrive stale after the block they were for, and some blocks lose the race to a rival block and are orphaned. The slower your link, the more work you lose. Network upgrades, unlocked at rig level 5, cut your latency."
+                        }
+                        p {
+                            "Shares and blocks are found by chance, just like real mining. Your hash rate sets how often you can expect them, the mining tab shows the expected time for each and how far past it you are. The coin details track your luck, lucky streaks, your fastest block and your longest drought."
+                        }
+                        p {
+                            "With multi mining you choose how your hash is split between the coins you mine: evenly, by a percentage you set on each mining tab, or in proportion to each coin's profit. Profit estimates follow the split."
+                        }
+                        p {
+                            "Established coins sometimes hard fork. The fork starts its own chain with its own price and difficulty, and anyone holding or staking the original gets the same amount of the new coin for free. Forks add new coins to the market, up to twelve."
+                        }
+                        p {
+                            "You are not alone in the market. Whales, momentum traders and panic sellers buy and sell alongside you and move prices, and their bigger trades make the news. Rival miners chase the most profitable coin, win blocks on it and push up its difficulty, leaving fewer blocks for you."
+                        }
+                        p {
+                            "The Info button on a coin opens its details: the price over its whole life, mining progress and difficulty, its age and rug chance, how its profit factor has moved and every trade you made in it."
+                        }
+                        p {
+                            "Click the star next to a coin to add it to your watchlist. Alerts for price levels, big daily moves or rug warning signs can be set from a coin's market window, and show up in the command line, the notifications window and, if you allow it, as browser notifications."
+                        }
+                        p {
+                            "Coins can be swapped straight into another coin from its market window at the cross rate of their prices, less a small spread. Swap All Coins routes every other coin you hold into that one."
+                        }
+                        p {
+                            "The Dashboard tab in the portfolio window tracks your net worth day by day, counting the bank, coins at market value, staked coins, margin positions, presales and your NFT studio."
+                        }
+                        p {
+                            "The coins table shows each coin's market cap rank, 24 hour volume and share of the whole market. Small caps are easier to rug, and thinning volume can be an early sign of one."
+                        }
+                        p {
+                            "Each coin's difficulty retargets every few blocks and drifts between them to match the hash on its network, which grows as the price climbs and as you mine it. Block rewards halve a few times over a coin's life, so the early blocks pay the most."
+                        }
+                        p {
+                            "New coins are announced as ICOs a few days before they list. Presale coins are bought at a fixed price up to the allocation cap and land in your balance when the coin lists. A listing can moon, hold steady, flop or be rugged on the spot, depending on what kind of coin it really is."
+                        }
+                        p {
+                            "Coins you hold can be staked for 3, 7 or 14 days to earn a daily yield paid into your balance. Unstaking before the term ends forfeits part of the stake, and staked coins are lost in a rug pull unless Rug Protection covers them."
                         }
+
+                        br {}
+
+                        h4 { "Good Luck!" }
                     }
-                    div {
-                        class: "flex flex-row",
-                        style: "justify-content: space-between;",
-                        button { class: "", onclick: close_modal, "Close" }
+
+                    button {
+                        class: "",
+                        style: "margin-top: 10px;",
+                        onclick: close_modal,
+                        "Close"
                     }
                 }
             }
@@ -3660,151 +4498,1478 @@ pub fn ImportExportModal(
 }
 
 #[component]
-pub fn BuyModal(
-    series: Signal<Vec<Vec<f64>>>,
-    series_labels: Signal<Vec<String>>,
-    labels: Signal<Vec<String>>,
-    confirm_modal: Signal<ConfirmModal>,
-) -> Element {
+pub fn Modal(confirm_modal: Signal<ConfirmModal>) -> Element {
     let close_modal = {
         move |_| {
-            BUY_MODAL.write().show = false;
-            BUY_MODAL.write().coin = None;
+            IS_PAUSED.write().toggle();
+            DO_SAVE.write().save = true;
+            if let Some(mut galaxy_save_details) = GALAXY_SAVE_DETAILS() {
+                if galaxy_save_details.active && galaxy_save_details.slot.is_some() {
+                    galaxy_save_details.force_save = true;
+                    *GALAXY_SAVE_DETAILS.write() = Some(galaxy_save_details);
+                }
+            }
         }
     };
 
-    let coin_name = {
-        let coin = BUY_MODAL().coin.clone();
+    let new_game = {
+        move |_| {
+            use_future(move || async move {
+                let mut confirm_modal = confirm_modal.clone();
 
-        match coin {
-            Some(coin) => coin.name,
-            None => "No Coin".to_string(),
+                let msg = "Are you sure you want to start a new game?".to_string();
+
+                confirm_modal.write().msg = msg;
+                confirm_modal.write().show = true;
+
+                let confirm = loop {
+                    let conf = confirm_modal().confirm;
+                    match conf {
+                        Some(conf) => {
+                            confirm_modal.write().confirm = None;
+                            break conf;
+                        }
+                        None => TimeoutFuture::new(100).await,
+                    }
+                };
+
+                if confirm {
+                    clear_game_state().await;
+                    clear_paint_undo().await;
+
+                    let galaxy_host = get_galaxy_host().await;
+
+                    match galaxy_host {
+                        Ok(galaxy_host) => match galaxy_host {
+                            Some(galaxy_host) => {
+                                if galaxy_host.galaxy && galaxy_host.logged_in {
+                                    if let Some(galaxy_save_details) = GALAXY_SAVE_DETAILS() {
+                                        if galaxy_save_details.active
+                                            && galaxy_save_details.slot.is_some()
+                                        {
+                                            info!("Deleting cloud save");
+
+                                            let save_slot = galaxy_save_details.slot.unwrap();
+                                            delete_cloud_save(save_slot).await;
+                                        }
+                                    };
+                                }
+                            }
+                            None => {}
+                        },
+                        Err(_) => {}
+                    }
+
+                    let win = window();
+
+                    win.location().reload().unwrap();
+                }
+            });
         }
     };
 
-    let coin_name_buy = coin_name.clone();
-    let coin_name_sell = coin_name.clone();
-    let coin_name_can_sell = coin_name.clone();
-    let coin_name_replace = coin_name.clone();
-    let coin_name_can_sell_max = coin_name.clone();
+    let show_help_modal = {
+        move || {
+            HELP_MODAL.write().show = true;
+        }
+    };
 
-    let max_buyable = {
-        let mkt = MARKET().clone();
-        let coin = mkt.coin_by_name(&coin_name);
-        let max_buyable = match coin {
-            Some(coin) => {
-                let amt = mkt.get_max_buyable(&coin);
-                if amt < 0.00001 {
-                    0.0
-                } else {
-                    amt
+    let show_import_export_modal = {
+        move || {
+            IMPORT_EXPORT_MODAL.write().show = true;
+        }
+    };
+
+    let auto_save_time_opts: Vec<u64> = Vec::from([5, 10, 15, 20, 30, 60, 90, 120, 180, 240, 300]);
+    let mut selected_time: Signal<u64> = use_signal(|| 30);
+
+    rsx! {
+        if IS_PAUSED().paused {
+            // Backdrop
+            div { class: "backdrop" }
+            // Modal content
+            div { class: "window modal pauseModal",
+                div { class: "title-bar",
+                    div { class: "title-bar-text", "Paused" }
+                    div { class: "title-bar-controls",
+                        button {
+                            class: "close",
+                            aria_label: "Help",
+                            onclick: move |_| {
+                                show_help_modal();
+                            },
+                            ""
+                        }
+                        button {
+                            class: "close",
+                            aria_label: "Close",
+                            onclick: close_modal,
+                            ""
+                        }
+                    }
+                }
+                div { class: "window-body ",
+                    div {
+                        class: "window",
+                        style: "margin-bottom: 10px;padding: 10px;text-align: center;min-width: 225px;",
+                        h3 { "Game Paused" }
+
+                        br {}
+
+                        h4 { "Hint" }
+                        p { "Add to your home screen to play offline." }
+
+                        br {}
+
+                        p {
+                            span { "Now available on " }
+                            a {
+                                href: "https://play.google.com/store/apps/details?id=lol.hashquest.twa",
+                                target: "_blank",
+                                style: "text-decoration:underline;",
+                                "Google Play"
+                            }
+                        }
+
+                        br {}
+
+                        if GALAXY_SAVE_DETAILS().is_some() {
+                            div { class: "flex flex-col",
+                                div {
+                                    input {
+                                        id: "cloud-save",
+                                        class: "",
+                                        style: "",
+                                        r#type: "checkbox",
+                                        checked: GALAXY_SAVE_DETAILS().as_ref().unwrap().active,
+                                        onclick: move |_| {
+                                            let toggle_autosave = toggle_autosave.clone();
+                                            async move {
+                                                toggle_autosave().await;
+                                            }
+                                        },
+                                        prevent_default: "onclick"
+                                    }
+                                    label { class: "", r#for: "cloud-save",
+                                        "Autosave to Galaxy.click Cloud"
+                                    }
+                                }
+                                if GALAXY_SAVE_DETAILS().as_ref().unwrap().active {
+                                    div {
+                                        style: "margin-top: 10px;",
+                                        class: "flex flex-col",
+                                        label { r#for: "auto-save-time",
+                                            "Auto Cloud Save Time (seconds): "
+                                        }
+                                        select {
+                                            id: "auto-save-time",
+                                            value: "{GALAXY_SAVE_DETAILS().unwrap().save_interval}",
+                                            oninput: move |event| {
+                                                if let Ok(value) = event.value().parse::<u64>() {
+                                                    selected_time.set(value);
+                                                    if let Some(mut galaxy_save_details) = GALAXY_SAVE_DETAILS() {
+                                                        if galaxy_save_details.active && galaxy_save_details.slot.is_some() {
+                                                            let save_interval = selected_time();
+                                                            galaxy_save_details.save_interval = save_interval;
+                                                            galaxy_save_details.force_save = true;
+                                                            *GALAXY_SAVE_DETAILS.write() = Some(galaxy_save_details);
+                                                            DO_SAVE.write().save = true;
+                                                        }
+                                                    }
+                                                }
+                                            },
+                                            // Generate the dropdown options
+                                            for time in auto_save_time_opts.iter() {
+                                                option {
+                                                    value: "{time}",
+                                                    selected: *time == selected_time(),
+                                                    "{time}"
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: space-between;",
+                            button {
+                                class: "",
+                                style: "margin-top: 10px;",
+                                onclick: move |_| {
+                                    show_help_modal();
+                                },
+                                "Help"
+                            }
+                            button {
+                                class: "",
+                                style: "margin-top: 10px;",
+                                onclick: move |_| {
+                                    show_import_export_modal();
+                                },
+                                "Import/Export"
+                            }
+                        }
+
+                        p { "Click Resume to continue your game." }
+                    }
+                    div {
+                        class: "flex flex-row",
+                        style: "justify-content: space-between;",
+                        button { class: "", onclick: close_modal, "Resume" }
+                        button { class: "", onclick: new_game, "New Game" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn ImportExportModal(
+    series: Signal<Vec<Vec<f64>>>,
+    series_labels: Signal<Vec<String>>,
+    labels: Signal<Vec<String>>,
+) -> Element {
+    let close_modal = {
+        move |_| {
+            IMPORT_EXPORT_MODAL.write().show = false;
+        }
+    };
+
+    let clear_textarea = {
+        move || {
+            let window = window();
+            let document = window.document().expect("document not found");
+            let textarea = document
+                .get_element_by_id("import-export-textarea")
+                .expect("textarea not found")
+                .dyn_into::<web_sys::HtmlTextAreaElement>()
+                .expect("textarea not found");
+            textarea.set_value("");
+        }
+    };
+
+    let export_game = {
+        move || {
+            use_future(move || async move {
+                let game_state_res = get_game_state().await;
+
+                let game_state_opt = match game_state_res {
+                    Ok(game_state) => game_state,
+                    Err(_) => None,
+                };
+
+                let game_state = match game_state_opt {
+                    Some(game_state) => game_state,
+                    None => {
+                        let _ = window().alert_with_message("Failed to export game data.");
+                        return;
+                    }
+                };
+
+                let game_state = export_game_state(&game_state).await;
+
+                match game_state {
+                    Some(game_state) => {
+                        let window = window();
+                        let clipboard = window.navigator().clipboard();
+
+                        let result: js_sys::Promise = clipboard.write_text(&game_state);
+                        let future = JsFuture::from(result);
+
+                        match future.await {
+                            Ok(_) => {
+                                spawn_local(async move {
+                                    command_line_output("Game data copied to clipboard.").await;
+                                });
+
+                                let document = window.document().expect("document not found");
+                                let export_button = document
+                                    .get_element_by_id("export-button")
+                                    .expect("export button not found")
+                                    .dyn_into::<web_sys::HtmlButtonElement>()
+                                    .expect("export button not found");
+
+                                let _ = window.alert_with_message("Game data copied to clipboard.\nUse this data to import your game later.\n\nKeep it safe!");
+
+                                export_button.set_disabled(true);
+                                export_button.set_inner_text("Copied");
+
+                                TimeoutFuture::new(2000).await;
+
+                                export_button.set_disabled(false);
+                                export_button.set_inner_text("Export");
+                            }
+                            Err(_) => {
+                                let _ = window
+                                    .alert_with_message("Failed to copy game data to clipboard.");
+                            }
+                        }
+                    }
+                    None => {}
+                }
+            })
+        }
+    };
+
+    let import_game_data = {
+        move || {
+            let win = window();
+            let document = win.document().expect("document not found");
+            let textarea = document
+                .get_element_by_id("import-export-textarea")
+                .expect("textarea not found")
+                .dyn_into::<web_sys::HtmlTextAreaElement>()
+                .expect("textarea not found");
+
+            let import_button = document
+                .get_element_by_id("import-button")
+                .expect("import button not found")
+                .dyn_into::<web_sys::HtmlButtonElement>()
+                .expect("import button not found");
+
+            import_button.set_disabled(true);
+            import_button.set_inner_text("Importing...");
+
+            let game_data = textarea.value();
+            let game_data = game_data.trim().to_string();
+            let game_clone = game_data.clone();
+
+            if game_data.is_empty() {
+                spawn_local(async move {
+                    command_line_output("No Game data to import.").await;
+                });
+
+                import_button.set_disabled(false);
+                import_button.set_inner_text("Import");
+
+                return;
+            }
+
+            use_future(move || {
+                let game_clone = game_clone.clone();
+                async move {
+                    let res = load_game_from_string(game_clone).await;
+                    let win = window();
+
+                    match res {
+                        true => {
+                            let _ = win.alert_with_message(
+                                "Game data imported successfully!\nThe game will now reload.",
+                            );
+                            win.location().reload().unwrap();
+                        }
+                        false => {
+                            let _ = win.alert_with_message(
+                                "Failed to import game data.\nPlease check the data and try again.",
+                            );
+
+                            let document = win.document().expect("document not found");
+
+                            let import_button = document
+                                .get_element_by_id("import-button")
+                                .expect("import button not found")
+                                .dyn_into::<web_sys::HtmlButtonElement>()
+                                .expect("import button not found");
+
+                            import_button.set_disabled(false);
+                            import_button.set_inner_text("Import");
+                        }
+                    }
+                }
+            });
+        }
+    };
+
+    rsx! {
+        if IMPORT_EXPORT_MODAL().show {
+            // Backdrop
+            div { class: "backdrop" }
+            // Modal content
+            div { class: "window modal pauseModal",
+                div { class: "title-bar",
+                    div { class: "title-bar-text", "Import/Export" }
+                    div { class: "title-bar-controls",
+                        button {
+                            class: "close",
+                            aria_label: "Close",
+                            onclick: close_modal,
+                            ""
+                        }
+                    }
+                }
+                div { class: "window-body ",
+                    div {
+                        class: "window",
+                        style: "margin-bottom: 10px;padding: 10px;text-align: center;min-width: 225px;",
+                        h3 { "Import/Export Game" }
+
+                        br {}
+
+                        p { style: "font-size: small;",
+                            "To import a game, paste your game data below."
+                        }
+                        textarea {
+                            id: "import-export-textarea",
+                            class: "w-full",
+                            style: "font-family: 'Consolas', 'Courier New', Courier, monospace;padding: 10px;line-height: 1.75;",
+                            cols: "30",
+                            resize: "none"
+                        }
+
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: space-between;",
+                            button {
+                                id: "import-button",
+                                class: "",
+                                style: "margin-top: 10px;",
+                                onclick: move |_| {
+                                    import_game_data();
+                                },
+                                "Import"
+                            }
+                            button {
+                                class: "",
+                                style: "margin-top: 10px;",
+                                onclick: move |_| {
+                                    clear_textarea();
+                                },
+                                "Clear"
+                            }
+                        }
+
+                        br {}
+
+                        p { style: "font-size: small;", "To export a game, click the button below." }
+                        p { style: "font-size: small;",
+                            "Save the copied data in a safe place to import your game later."
+                        }
+
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: space-between;",
+                            button {
+                                id: "export-button",
+                                class: "",
+                                style: "margin-top: 10px;",
+                                onclick: move |_| {
+                                    export_game();
+                                },
+                                "Export"
+                            }
+                        }
+                        p { style: "font-size: small;margin-top: 10px;",
+                            span { "We recommend using " }
+                            span {
+                                a {
+                                    href: "https://e2epaste.xyz",
+                                    target: "_blank",
+                                    "e2epaste.xyz"
+                                }
+                            }
+                            span { " to securly transfer your game data to a different device." }
+                        }
+                    }
+                    div {
+                        class: "flex flex-row",
+                        style: "justify-content: space-between;",
+                        button { class: "", onclick: close_modal, "Close" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn BuyModal(
+    series: Signal<Vec<Vec<f64>>>,
+    series_labels: Signal<Vec<String>>,
+    labels: Signal<Vec<String>>,
+    confirm_modal: Signal<ConfirmModal>,
+) -> Element {
+    let close_modal = {
+        move |_| {
+            BUY_MODAL.write().show = false;
+            BUY_MODAL.write().coin = None;
+        }
+    };
+
+    let coin_id: Option<CoinId> = BUY_MODAL().coin.map(|c| c.id());
+
+    let coin_name = {
+        let coin = BUY_MODAL().coin.clone();
+
+        match coin {
+            Some(coin) => coin.name,
+            None => "No Coin".to_string(),
+        }
+    };
+
+    let coin_name_buy = coin_name.clone();
+    let coin_name_replace = coin_name.clone();
+
+    let max_buyable = {
+        let mkt = MARKET().clone();
+        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+        let max_buyable = match coin {
+            Some(coin) => {
+                let amt = mkt.get_max_buyable(&coin);
+                if amt < 0.00001 {
+                    0.0
+                } else {
+                    amt
+                }
+            }
+            None => 0.0,
+        };
+        max_buyable
+    };
+
+    let can_buy_amount = move |amount| {
+        if max_buyable < 0.00001 {
+            return false;
+        }
+        max_buyable >= amount
+    };
+
+    let do_buy = move |amount, do_max| {
+        let mkt = MARKET();
+
+        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+        let mut mkt_mut = MARKET.write();
+        match coin {
+            Some(coin) => {
+                let buy_res = if do_max {
+                    mkt_mut.buy_max_coin(&coin)
+                } else {
+                    mkt_mut.buy_coin(&coin, amount)
+                };
+
+                let msg = if buy_res {
+                    format!("Purchase of {amount} {coin_name_buy} successful.")
+                } else {
+                    format!("Purchase of {amount} {coin_name_buy} failed.")
+                };
+                spawn_local(async move {
+                    command_line_output(&msg).await;
+                });
+
+                DO_SAVE.write().save = true;
+            }
+            None => {}
+        }
+    };
+
+    let can_sell_amount = move |amount| {
+        let mkt = MARKET();
+        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+        match coin {
+            Some(coin) => coin.balance >= amount,
+            None => false,
+        }
+    };
+
+    let do_sell = move |amount, do_max| {
+        let mkt = MARKET();
+        let mut mut_mkt = MARKET.write();
+        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+
+        match coin {
+            Some(coin) => {
+                let amount = if do_max { coin.balance } else { amount };
+                let amount_opt = if do_max { None } else { Some(amount) };
+
+                let price = coin.current_price;
+                let total = amount * price;
+                let name = coin.name.clone();
+                if amount > 0.0 {
+                    mut_mkt.sell_coins(&coin, amount_opt);
+                    DO_SAVE.write().save = true;
+                    let msg = format!("Sold {amount} {name} for ${total}");
+                    spawn_local(async move {
+                        command_line_output(&msg).await;
+                    });
+
+                    DO_SAVE.write().save = true;
+                }
+            }
+            None => {}
+        }
+    };
+
+    let coin_balance = {
+        let mkt = MARKET().clone();
+        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+        let coin_balance = match coin {
+            Some(coin) => coin.balance,
+            None => 0.0,
+        };
+        coin_balance
+    };
+
+    let coin_price = {
+        let mkt = MARKET().clone();
+        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+        let coin_price = match coin {
+            Some(coin) => coin.current_price,
+            None => 0.0,
+        };
+        coin_price
+    };
+
+    let coin_hint = {
+        let mkt = MARKET().clone();
+        match coin_id.and_then(|id| mkt.coin_by_id(id)) {
+            Some(coin) => coin.hint(),
+            None => "Unknown",
+        }
+    };
+
+    let buy_modal_coin = {
+        let mkt = MARKET().clone();
+        coin_id.and_then(|id| mkt.coin_by_id(id)).cloned()
+    };
+
+    let mut leverage = use_signal(|| LEVERAGE_OPTIONS[0]);
+
+    let margin_positions = match coin_id {
+        Some(id) => MARKET().margin().positions_for(id),
+        None => Vec::new(),
+    };
+
+    let short_position = margin_positions
+        .iter()
+        .find(|p| p.kind == MarginKind::Short)
+        .cloned();
+    let long_position = margin_positions
+        .iter()
+        .find(|p| p.kind == MarginKind::Long)
+        .cloned();
+
+    let margin_preview = move |kind: MarginKind, amount: f64| {
+        let lev = leverage();
+        let collateral = MarginAccount::required_collateral(kind, amount, coin_price, lev);
+        let position = MarginPosition::new(
+            kind,
+            CoinId::default(),
+            "",
+            amount,
+            coin_price,
+            collateral,
+            0,
+        );
+        (collateral, position.liquidation_price())
+    };
+
+    let can_open_margin = move |kind: MarginKind, amount: f64| {
+        let (collateral, _) = margin_preview(kind, amount);
+        coin_price > 0.0 && MARKET().bank.balance >= collateral
+    };
+
+    let do_open_margin = move |kind: MarginKind, amount: f64| {
+        let mkt = MARKET();
+        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+
+        if let Some(coin) = coin {
+            let opened = MARKET.write().open_margin(coin, kind, amount, leverage());
+
+            let msg = if opened {
+                format!("Opened {} of {amount} {}.", kind.label(), coin.name)
+            } else {
+                format!("{} of {amount} {} failed.", kind.label(), coin.name)
+            };
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+
+            DO_SAVE.write().save = true;
+        }
+    };
+
+    let do_close_margin = move |kind: MarginKind| {
+        if let Some(id) = coin_id {
+            if let Some(pnl) = MARKET.write().close_margin(id, kind) {
+                let msg = format!(
+                    "Closed {} for ${} profit.",
+                    kind.label(),
+                    format_comma_seperator(pnl, 2)
+                );
+                spawn_local(async move {
+                    command_line_output(&msg).await;
+                });
+
+                DO_SAVE.write().save = true;
+            }
+        }
+    };
+
+    let mut swap_from: Signal<Option<CoinId>> = use_signal(|| None);
+
+    let swap_sources: Vec<CryptoCoin> = MARKET()
+        .coins
+        .iter()
+        .filter(|c| c.active && c.balance > 0.0 && Some(c.id()) != coin_id)
+        .cloned()
+        .collect();
+
+    let swap_source = swap_sources
+        .iter()
+        .find(|c| Some(c.id()) == swap_from())
+        .or(swap_sources.first())
+        .cloned();
+
+    let swap_rate = match (&swap_source, coin_id) {
+        (Some(source), Some(id)) => MARKET().swap_quote(source.id(), id, 1.0).map(|q| q.rate),
+        _ => None,
+    };
+
+    let source_id = swap_source.as_ref().map(|c| c.id());
+    let source_balance = swap_source.as_ref().map(|c| c.balance).unwrap_or(0.0);
+
+    let do_swap = move |amount: f64| {
+        if let (Some(from), Some(to)) = (source_id, coin_id) {
+            let msg = match MARKET.write().swap(from, to, amount) {
+                Some(quote) => format!(
+                    "Swapped {} {} for {} {}.",
+                    format_comma_seperator(quote.amount_in, 5),
+                    quote.from_name,
+                    format_comma_seperator(quote.amount_out, 5),
+                    quote.to_name
+                ),
+                None => "Swap failed.".to_string(),
+            };
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+
+            DO_SAVE.write().save = true;
+        }
+    };
+
+    let do_swap_all = move |_| {
+        if let Some(to) = coin_id {
+            let quotes = MARKET.write().swap_all_into(to);
+            let received: f64 = quotes.iter().map(|q| q.amount_out).sum();
+
+            let msg = format!(
+                "Swapped {} coins into {} {}.",
+                quotes.len(),
+                format_comma_seperator(received, 5),
+                quotes
+                    .first()
+                    .map(|q| q.to_name.clone())
+                    .unwrap_or_default()
+            );
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+
+            DO_SAVE.write().save = true;
+        }
+    };
+
+    let mut alert_rule = use_signal(|| AlertRule::PriceAbove(0.0));
+    let mut alert_value = use_signal(|| 0.0);
+
+    let coin_alerts = match coin_id {
+        Some(id) => MARKET().alerts().alerts_for(id),
+        None => Vec::new(),
+    };
+
+    let do_add_alert = move |_| {
+        let mkt = MARKET();
+        if let Some(coin) = coin_id.and_then(|id| mkt.coin_by_id(id)) {
+            let rule = alert_rule().with_value(alert_value());
+            MARKET.write().alerts_mut().add_alert(coin, rule);
+
+            let msg = format!("Alert set: {} {}.", coin.name, rule.label().to_lowercase());
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+
+            DO_SAVE.write().save = true;
+        }
+    };
+
+    let mut stake_term = use_signal(|| StakeTerm::Short);
+
+    let coin_stake = buy_modal_coin.as_ref().and_then(|c| c.stake.clone());
+
+    // Top-ups join the running stake, so its term wins over the picker
+    let active_term = match &coin_stake {
+        Some(stake) => stake.term,
+        None => stake_term(),
+    };
+
+    // Rate the selected term would pay on this coin
+    let stake_preview_apy = {
+        let multiplier = match buy_modal_coin.as_ref().and_then(|c| c.profile()) {
+            Some(profile) => profile.reward_multiplier,
+            None => 1.0,
+        };
+        Stake::new(0.0, stake_term(), multiplier, 0).apy()
+    };
+
+    let unstake_penalty = match &coin_stake {
+        Some(stake) => stake.early_penalty(GAME_TIME().day),
+        None => 0.0,
+    };
+
+    let do_stake = move |amount: f64, do_max: bool| {
+        if let Some(id) = coin_id {
+            let coin_name = MARKET().coin_by_id(id).map(|c| c.name.clone());
+            let coin_name = coin_name.unwrap_or_default();
+            let amount = if do_max { coin_balance } else { amount };
+            let running = MARKET()
+                .coin_by_id(id)
+                .and_then(|c| c.stake.as_ref().map(|s| s.term));
+            let term = running.unwrap_or(stake_term());
+            let staked = MARKET.write().stake_coin(id, amount, term);
+
+            let msg = if staked && running.is_some() {
+                format!("Added {amount} {coin_name} to your {} stake.", term.label())
+            } else if staked {
+                format!("Staked {amount} {coin_name} for {}.", term.label())
+            } else {
+                format!("Staking {amount} {coin_name} failed.")
+            };
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+
+            DO_SAVE.write().save = true;
+        }
+    };
+
+    let do_unstake = move |_| {
+        if let Some(id) = coin_id {
+            let coin_name = MARKET().coin_by_id(id).map(|c| c.name.clone());
+            let coin_name = coin_name.unwrap_or_default();
+            if let Some((returned, penalty)) = MARKET.write().unstake_coin(id) {
+                let msg = if penalty > 0.0 {
+                    format!(
+                        "Unstaked {} {coin_name} early, {} forfeited.",
+                        format_comma_seperator(returned, 5),
+                        format_comma_seperator(penalty, 5)
+                    )
+                } else {
+                    format!(
+                        "Unstaked {} {coin_name}.",
+                        format_comma_seperator(returned, 5)
+                    )
+                };
+                spawn_local(async move {
+                    command_line_output(&msg).await;
+                });
+
+                DO_SAVE.write().save = true;
+            }
+        }
+    };
+
+    let (short_preview_margin, short_preview_liq) = margin_preview(MarginKind::Short, 10.0);
+    let (long_preview_margin, long_preview_liq) = margin_preview(MarginKind::Long, 10.0);
+
+    let coin_warnings = {
+        let mkt = MARKET().clone();
+        match coin_id.and_then(|id| mkt.coin_by_id(id)) {
+            Some(coin) => coin.rug_signals(MINING_RIG().get_due_diligence_level()),
+            None => Vec::new(),
+        }
+    };
+
+    rsx! {
+        if BUY_MODAL().show {
+            // Backdrop
+            div { class: "backdrop", onclick: close_modal }
+            // Modal content
+            div { class: "window modal pauseModal",
+                div { class: "title-bar",
+                    div { class: "title-bar-text", "Market" }
+                    div { class: "title-bar-controls",
+                        button {
+                            class: "close",
+                            aria_label: "Close",
+                            onclick: close_modal,
+                            ""
+                        }
+                    }
+                }
+                div { class: "window-body ",
+                    div {
+                        class: "window",
+                        style: "margin-bottom: 10px;padding: 10px;text-align: center;min-width: 225px;",
+                        if let Some(coin) = buy_modal_coin {
+                            div {
+                                class: "flex flex-row",
+                                style: "justify-content: center;align-items: center;gap: 6px;",
+                                CoinIcon { coin: coin.clone() }
+                                h3 { "{coin_name} ({coin.get_ticker()}) Market" }
+                            }
+                            p { class: "coin-hint", "{coin.get_pitch()}" }
+                            button {
+                                style: "margin-top: 5px;",
+                                onclick: {
+                                    let id = coin.id();
+                                    move |_| {
+                                        COIN_DETAIL_MODAL.write().coin = Some(id);
+                                        COIN_DETAIL_MODAL.write().show = true;
+                                    }
+                                },
+                                "Details"
+                            }
+                        } else {
+                            h3 { "{coin_name} Market" }
+                        }
+                        p { class: "coin-hint", "Profile: {coin_hint}" }
+                        for signal in coin_warnings.iter() {
+                            p { class: "coin-warning", "⚠ {signal.label()}" }
+                        }
+                        br {}
+                        p { style: "font-size:small;",
+                            "Current Price: ${format_comma_seperator(coin_price, 2)}"
+                        }
+                        p { style: "font-size:small;",
+                            "Bank Balance: ${format_comma_seperator(MARKET().bank.balance, 5)}"
+                        }
+                        p { style: "font-size:small;",
+                            "Max Purchase: {format_comma_seperator(max_buyable, 5)}"
+                        }
+                        p { style: "font-size:small;",
+                            "Coin Balance: {format_comma_seperator(coin_balance, 5)}"
+                        }
+                        br {}
+                        p { style: "font-size: medium;", "Buy" }
+                        div {
+                            class: "market-buttons",
+                            style: "justify-content: space-between;margin-bottom: 10px;",
+                            button {
+                                class: "sell-btn market",
+                                disabled: !can_buy_amount(1.0),
+                                onclick: {
+                                    let do_buy = do_buy.clone();
+                                    move |_| {
+                                        do_buy(1.0, false);
+                                    }
+                                },
+                                "+1"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: !can_buy_amount(10.0),
+                                onclick: {
+                                    let do_buy = do_buy.clone();
+                                    move |_| {
+                                        do_buy(10.0, false);
+                                    }
+                                },
+                                "+10"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: !can_buy_amount(100.0),
+                                onclick: {
+                                    let do_buy = do_buy.clone();
+                                    move |_| {
+                                        do_buy(100.0, false);
+                                    }
+                                },
+                                "+100"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: !can_buy_amount(max_buyable),
+                                onclick: {
+                                    let do_buy = do_buy.clone();
+                                    move |_| {
+                                        do_buy(max_buyable, true);
+                                    }
+                                },
+                                "Max"
+                            }
+                        }
+                        p { style: "font-size: medium;", "Sell" }
+                        div {
+                            class: "market-buttons",
+                            style: "justify-content: space-between;",
+                            button {
+                                class: "sell-btn market",
+                                disabled: !can_sell_amount(1.0),
+                                onclick: {
+                                    move |_| {
+                                        do_sell(1.0, false);
+                                    }
+                                },
+                                "-1"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: !can_sell_amount(10.0),
+                                onclick: {
+                                    move |_| {
+                                        do_sell(10.0, false);
+                                    }
+                                },
+                                "-10"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: !can_sell_amount(100.0),
+                                onclick: {
+                                    move |_| {
+                                        do_sell(100.0, false);
+                                    }
+                                },
+                                "-100"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: {
+                                    let mkt = MARKET().clone();
+                                    let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+                                    match coin {
+                                        Some(coin) => coin.balance <= 0.0,
+                                        None => true,
+                                    }
+                                },
+                                onclick: {
+                                    move |_| {
+                                        do_sell(max_buyable, true);
+                                    }
+                                },
+                                "Max"
+                            }
+                        }
+                        br {}
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: space-between;align-items: center;",
+                            p { style: "font-size: medium;", "Swap Into" }
+                            select {
+                                class: "select",
+                                disabled: swap_sources.is_empty(),
+                                onchange: move |e| {
+                                    swap_from.set(e.value().parse::<u64>().ok().map(CoinId));
+                                },
+                                for source in swap_sources.iter() {
+                                    option {
+                                        value: "{source.id()}",
+                                        selected: Some(source.id()) == source_id,
+                                        "{source.name}"
+                                    }
+                                }
+                            }
+                        }
+                        if let (Some(source), Some(rate)) = (&swap_source, swap_rate) {
+                            p { style: "font-size:small;",
+                                "1 {source.name} = {format_comma_seperator(rate, 5)} {coin_name} ({format_comma_seperator(SWAP_SPREAD * 100.0, 0)}% spread)"
+                            }
+                        } else {
+                            p { style: "font-size:small;", "No other coins to swap" }
+                        }
+                        div {
+                            class: "market-buttons",
+                            style: "justify-content: space-between;margin-bottom: 10px;",
+                            for amount in [10.0, 100.0] {
+                                button {
+                                    class: "sell-btn market",
+                                    disabled: source_balance < amount,
+                                    onclick: move |_| do_swap(amount),
+                                    "Swap {amount}"
+                                }
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: source_balance <= 0.0,
+                                onclick: move |_| do_swap(source_balance),
+                                "Swap Max"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: swap_sources.is_empty(),
+                                onclick: do_swap_all,
+                                "Swap All Coins"
+                            }
+                        }
+                        br {}
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: space-between;align-items: center;",
+                            p { style: "font-size: medium;", "Margin" }
+                            select {
+                                class: "select",
+                                onchange: move |e| {
+                                    if let Ok(lev) = e.value().parse::<f64>() {
+                                        leverage.set(lev);
+                                    }
+                                },
+                                for lev in LEVERAGE_OPTIONS.iter() {
+                                    option {
+                                        value: "{lev}",
+                                        selected: *lev == leverage(),
+                                        "{lev}x Long"
+                                    }
+                                }
+                            }
+                        }
+                        p { style: "font-size:small;",
+                            "Short 10: ${format_comma_seperator(short_preview_margin, 2)} margin, liq. ${format_comma_seperator(short_preview_liq, 2)}"
+                        }
+                        p { style: "font-size:small;",
+                            "Long 10: ${format_comma_seperator(long_preview_margin, 2)} margin, liq. ${format_comma_seperator(long_preview_liq, 2)}"
+                        }
+                        for position in [short_position.clone(), long_position.clone()].into_iter().flatten() {
+                            p { style: "font-size:small;",
+                                "{position.kind.label()} {format_comma_seperator(position.amount, 2)} @ ${format_comma_seperator(position.entry_price, 2)} | P&L ${format_comma_seperator(position.pnl(coin_price), 2)} | Liq. ${format_comma_seperator(position.liquidation_price(), 2)}"
+                            }
+                        }
+                        div {
+                            class: "market-buttons",
+                            style: "justify-content: space-between;margin-bottom: 10px;",
+                            for amount in [1.0, 10.0, 100.0] {
+                                button {
+                                    class: "sell-btn market",
+                                    disabled: !can_open_margin(MarginKind::Short, amount),
+                                    onclick: move |_| do_open_margin(MarginKind::Short, amount),
+                                    "Short {amount}"
+                                }
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: short_position.is_none(),
+                                onclick: move |_| do_close_margin(MarginKind::Short),
+                                "Cover"
+                            }
+                        }
+                        div {
+                            class: "market-buttons",
+                            style: "justify-content: space-between;",
+                            for amount in [1.0, 10.0, 100.0] {
+                                button {
+                                    class: "sell-btn market",
+                                    disabled: !can_open_margin(MarginKind::Long, amount),
+                                    onclick: move |_| do_open_margin(MarginKind::Long, amount),
+                                    "Long {amount}"
+                                }
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: long_position.is_none(),
+                                onclick: move |_| do_close_margin(MarginKind::Long),
+                                "Close"
+                            }
+                        }
+                        br {}
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: space-between;align-items: center;gap: 4px;",
+                            p { style: "font-size: medium;", "Alerts" }
+                            select {
+                                class: "select",
+                                onchange: move |e| {
+                                    if let Some(rule) = AlertRule::all()
+                                        .into_iter()
+                                        .find(|r| r.kind_label() == e.value())
+                                    {
+                                        alert_rule.set(rule);
+                                    }
+                                },
+                                for rule in AlertRule::all() {
+                                    option {
+                                        value: "{rule.kind_label()}",
+                                        selected: rule.kind_label() == alert_rule().kind_label(),
+                                        "{rule.kind_label()}"
+                                    }
+                                }
+                            }
+                            if alert_rule() != AlertRule::RugSignal {
+                                input {
+                                    r#type: "number",
+                                    style: "width: 70px;",
+                                    min: "0",
+                                    step: "any",
+                                    value: "{alert_value}",
+                                    oninput: move |e| {
+                                        if let Ok(value) = e.value().parse::<f64>() {
+                                            alert_value.set(value);
+                                        }
+                                    }
+                                }
+                            }
+                            button { class: "sell-btn market", onclick: do_add_alert, "Add" }
+                        }
+                        for alert in coin_alerts.iter() {
+                            div {
+                                class: "flex flex-row",
+                                style: "justify-content: space-between;align-items: center;",
+                                p { style: "font-size:small;",
+                                    "{alert.rule.label()} | fired {alert.fired}x"
+                                }
+                                button {
+                                    class: "sell-btn",
+                                    onclick: {
+                                        let id = alert.id;
+                                        move |_| {
+                                            MARKET.write().alerts_mut().remove_alert(id);
+                                            DO_SAVE.write().save = true;
+                                        }
+                                    },
+                                    "Remove"
+                                }
+                            }
+                        }
+                        br {}
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: space-between;align-items: center;",
+                            p { style: "font-size: medium;", "Staking" }
+                            select {
+                                class: "select",
+                                disabled: coin_stake.is_some(),
+                                onchange: move |e| {
+                                    if let Some(term) = StakeTerm::all()
+                                        .into_iter()
+                                        .find(|t| t.label() == e.value())
+                                    {
+                                        stake_term.set(term);
+                                    }
+                                },
+                                for term in StakeTerm::all() {
+                                    option {
+                                        value: "{term.label()}",
+                                        selected: term == active_term,
+                                        "{term.label()}"
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(stake) = &coin_stake {
+                            p { style: "font-size:small;",
+                                "Staked {format_comma_seperator(stake.amount, 5)} | APY {format_comma_seperator(stake.apy() * 100.0, 1)}% | matures day {stake.maturity_day()}"
+                            }
+                            p { style: "font-size:small;",
+                                "Earned {format_comma_seperator(stake.earned, 5)} | early unstake forfeits {format_comma_seperator(unstake_penalty, 5)}"
+                            }
+                            p { style: "font-size:small;",
+                                "More coins join this {stake.term.label()} stake, unstake first to pick another term."
+                            }
+                        } else {
+                            p { style: "font-size:small;",
+                                "APY {format_comma_seperator(stake_preview_apy * 100.0, 1)}%, {format_comma_seperator(EARLY_UNSTAKE_PENALTY * 100.0, 0)}% penalty to unstake early"
+                            }
+                        }
+                        div {
+                            class: "market-buttons",
+                            style: "justify-content: space-between;",
+                            for amount in [10.0, 100.0] {
+                                button {
+                                    class: "sell-btn market",
+                                    disabled: coin_balance < amount,
+                                    onclick: move |_| do_stake(amount, false),
+                                    "Stake {amount}"
+                                }
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: coin_balance <= 0.0,
+                                onclick: move |_| do_stake(0.0, true),
+                                "Stake Max"
+                            }
+                            button {
+                                class: "sell-btn market",
+                                disabled: coin_stake.is_none(),
+                                onclick: do_unstake,
+                                "Unstake"
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex flex-row",
+                        style: "justify-content: space-between;",
+                        button { class: "", onclick: close_modal, "Close" }
+                        button {
+                            class: "",
+                            disabled: {
+                                let mkt = MARKET().clone();
+                                let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+                                if coin.is_some() {
+                                    let coin = coin.unwrap();
+                                    let new_coin_cooldown = MINING_RIG().get_new_coin_cooldown();
+                                    if new_coin_cooldown == 0 {
+                                        if coin.balance > 0.0 { true } else { false }
+                                    } else {
+                                        true
+                                    }
+                                } else {
+                                    true
+                                }
+                            },
+                            onclick: move |_| {
+                                let coin_name_replace = coin_name_replace.clone();
+                                let mut confirm_modal = confirm_modal.clone();
+                                async move {
+                                    let msg = format!(
+                                        "Are you sure you want to dismiss {}?\n\nThis action cannot be undone.",
+                                        coin_name_replace,
+                                    );
+                                    confirm_modal.write().msg = msg;
+                                    confirm_modal.write().show = true;
+                                    let confirm = loop {
+                                        let conf = confirm_modal().confirm;
+                                        match conf {
+                                            Some(confirm) => {
+                                                confirm_modal.write().confirm = None;
+                                                break confirm;
+                                            }
+                                            None => {
+                                                TimeoutFuture::new(100).await;
+                                            }
+                                        };
+                                    };
+                                    if confirm {
+                                        let mut series_labels = series_labels.clone();
+                                        let mut series = series.clone();
+                                        let mut labels = labels.clone();
+                                        let rig_lvl = MINING_RIG().get_level();
+                                        let day = GAME_TIME().day;
+                                        let coin_name = coin_name_replace.clone();
+                                        let mkt = MARKET().clone();
+                                        let coin = coin_id.and_then(|id| mkt.coin_by_id(id));
+                                        let coin = match coin {
+                                            Some(coin) => coin,
+                                            None => return,
+                                        };
+                                        let new_id = replace_coin(coin.id(), &mut series_labels, &mut series, rig_lvl, day);
+                                        MINING_RIG.write().set_new_coin_cooldown();
+                                        let new_coin = new_id.and_then(|id| MARKET().coin_by_id(id).cloned());
+                                        if let Some(coin) = new_coin {
+                                            run_sim_one_day_single(&mut series, &mut labels, &coin);
+                                        }
+                                        let msg = format!("Dismissed {coin_name}");
+                                        spawn_local(async move {
+                                            command_line_output(&msg).await;
+                                        });
+                                        BUY_MODAL.write().show = false;
+                                        BUY_MODAL.write().coin = None;
+                                        DO_SAVE.write().save = true;
+                                    }
+                                }
+                            },
+                            "Dismiss Coin"
+                        }
+                    }
                 }
             }
-            None => 0.0,
-        };
-        max_buyable
-    };
+        }
+    }
+}
 
-    let can_buy_amount = move |amount| {
-        if max_buyable < 0.00001 {
-            return false;
+#[component]
+pub fn CoinDetailModal() -> Element {
+    let close_modal = {
+        move |_| {
+            COIN_DETAIL_MODAL.write().show = false;
+            COIN_DETAIL_MODAL.write().coin = None;
         }
-        max_buyable >= amount
     };
 
-    let do_buy = move |amount, do_max| {
+    let coin = {
         let mkt = MARKET();
-
-        let coin = mkt.coin_by_name(&coin_name_buy);
-        let mut mkt_mut = MARKET.write();
-        match coin {
-            Some(coin) => {
-                let buy_res = if do_max {
-                    mkt_mut.buy_max_coin(&coin)
-                } else {
-                    mkt_mut.buy_coin(&coin, amount)
-                };
-
-                let msg = if buy_res {
-                    format!("Purchase of {amount} {coin_name_buy} successful.")
-                } else {
-                    format!("Purchase of {amount} {coin_name_buy} failed.")
-                };
-                spawn_local(async move {
-                    command_line_output(&msg).await;
-                });
-
-                DO_SAVE.write().save = true;
-            }
-            None => {}
-        }
+        COIN_DETAIL_MODAL()
+            .coin
+            .and_then(|id| mkt.coin_by_id(id))
+            .cloned()
     };
 
-    let can_sell_amount = move |amount| {
-        let mkt = MARKET();
-        let coin = mkt.coin_by_name(&coin_name_can_sell);
-        match coin {
-            Some(coin) => coin.balance >= amount,
-            None => false,
-        }
+    let coin = match coin {
+        Some(coin) => coin,
+        None => return rsx! {},
     };
 
-    let do_sell = move |amount, do_max| {
-        let mkt = MARKET();
-        let mut mut_mkt = MARKET.write();
-        let coin = mkt.coin_by_name(&coin_name_sell);
+    let candles = coin.candles(None);
+    let (price_series, price_labels) = if candles.len() > 1 {
+        (
+            candles.iter().map(|c| c.close as f32).collect::<Vec<f32>>(),
+            candle_labels(&candles),
+        )
+    } else {
+        (
+            coin.prices.iter().map(|p| *p as f32).collect::<Vec<f32>>(),
+            vec![String::new(); coin.prices.len()],
+        )
+    };
 
-        match coin {
-            Some(coin) => {
-                let amount = if do_max { coin.balance } else { amount };
-                let amount_opt = if do_max { None } else { Some(amount) };
+    let all_time_high = candles
+        .iter()
+        .map(|c| c.high)
+        .chain(coin.prices.iter().copied())
+        .fold(0.0, f64::max);
+    let all_time_low = candles
+        .iter()
+        .map(|c| c.low)
+        .chain(coin.prices.iter().copied())
+        .fold(f64::MAX, f64::min);
+
+    let profit_series = coin
+        .profit_history
+        .clone()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p as f32)
+        .collect::<Vec<f32>>();
+    let profit_labels = vec![String::new(); profit_series.len()];
+
+    let forked_from = coin.forked_from.map(|id| match MARKET().coin_by_id(id) {
+        Some(parent) => parent.name.clone(),
+        None => "a delisted coin".to_string(),
+    });
 
-                let price = coin.current_price;
-                let total = amount * price;
-                let name = coin.name.clone();
-                if amount > 0.0 {
-                    mut_mkt.sell_coins(&coin, amount_opt);
-                    DO_SAVE.write().save = true;
-                    let msg = format!("Sold {amount} {name} for ${total}");
-                    spawn_local(async move {
-                        command_line_output(&msg).await;
-                    });
+    let npcs = MARKET().npcs();
+    let rivals = npcs
+        .rivals_on(coin.id())
+        .iter()
+        .map(|n| n.name.clone())
+        .collect::<Vec<String>>();
+    let npc_flow = npcs.flow_for(coin.id());
 
-                    DO_SAVE.write().save = true;
-                }
-            }
-            None => {}
-        }
+    let hash_rate = MINING_RIG().get_hash_rate();
+    let block_percent = coin.blocks as f64 / coin.max_blocks.max(1) as f64 * 100.0;
+    let discovery = coin.discovery();
+    let share_percent = coin.get_share_effort().min(1.0) * 100.0;
+    let next_block_percent = coin.get_block_effort().min(1.0) * 100.0;
+    let fastest_block = match discovery.fastest_block {
+        Some(ticks) => format_eta(Some(ticks as f64 / discovery::TICKS_PER_SECOND)),
+        None => "-".to_string(),
     };
+    let longest_drought = format_eta(Some(
+        discovery.longest_drought as f64 / discovery::TICKS_PER_SECOND,
+    ));
 
-    let coin_balance = {
-        let mkt = MARKET().clone();
-        let coin = mkt.coin_by_name(&coin_name);
-        let coin_balance = match coin {
-            Some(coin) => coin.balance,
-            None => 0.0,
-        };
-        coin_balance
-    };
+    let ledger = MARKET().ledger();
+    let position = ledger.position(coin.id()).cloned();
+    let trades = ledger.entries_for_coin(coin.id());
 
-    let coin_price = {
-        let mkt = MARKET().clone();
-        let coin = mkt.coin_by_name(&coin_name);
-        let coin_price = match coin {
-            Some(coin) => coin.current_price,
-            None => 0.0,
-        };
-        coin_price
+    let pnl_style = |v: f64| {
+        if v < 0.0 {
+            "padding: 3px;color: #a00;"
+        } else {
+            "padding: 3px;color: #070;"
+        }
     };
 
     rsx! {
-        if BUY_MODAL().show {
+        if COIN_DETAIL_MODAL().show {
             // Backdrop
             div { class: "backdrop", onclick: close_modal }
             // Modal content
             div { class: "window modal pauseModal",
                 div { class: "title-bar",
-                    div { class: "title-bar-text", "Market" }
+                    div { class: "title-bar-text", "Coin Details" }
                     div { class: "title-bar-controls",
                         button {
                             class: "close",
@@ -3814,209 +5979,254 @@ pub fn BuyModal(
                         }
                     }
                 }
-                div { class: "window-body ",
+                div { class: "window-body",
                     div {
                         class: "window",
-                        style: "margin-bottom: 10px;padding: 10px;text-align: center;min-width: 225px;",
-                        h3 { "{coin_name} Market" }
-                        br {}
+                        style: "margin-bottom: 10px;padding: 10px;min-width: 225px;",
+                        div {
+                            class: "flex flex-row",
+                            style: "justify-content: center;align-items: center;gap: 6px;",
+                            CoinIcon { coin: coin.clone() }
+                            h3 { "{coin.name} ({coin.get_ticker()})" }
+                        }
+                        p { class: "coin-hint", style: "text-align: center;",
+                            "Profile: {coin.hint()}"
+                        }
+                        if let Some(parent) = forked_from {
+                            p { class: "coin-hint", style: "text-align: center;",
+                                "Hard fork of {parent}"
+                            }
+                        }
+                        if !coin.active {
+                            p { class: "coin-warning", style: "text-align: center;",
+                                "Delisted on day {coin.death_date.unwrap_or_default()}"
+                            }
+                        }
+
+                        h4 { "Price" }
+                        if price_series.len() > 1 {
+                            LineChart {
+                                padding_top: 20,
+                                padding_left: 70,
+                                padding_right: 20,
+                                padding_bottom: 30,
+                                height: "150px",
+                                series: vec![price_series],
+                                labels: price_labels,
+                                label_interpolation: (|v| format!("${}", format_compact(v as f64))) as fn(f32) -> String,
+                                show_dots: false,
+                                show_grid: false,
+                                line_width: "0.5%",
+                                max_ticks: 6
+                            }
+                        }
                         p { style: "font-size:small;",
-                            "Current Price: ${format_comma_seperator(coin_price, 2)}"
+                            "Current: ${format_comma_seperator(coin.current_price, 2)} | Launch: ${format_comma_seperator(coin.initial_price, 2)}"
                         }
                         p { style: "font-size:small;",
-                            "Bank Balance: ${format_comma_seperator(MARKET().bank.balance, 5)}"
+                            "High: ${format_comma_seperator(all_time_high, 2)} | Low: ${format_comma_seperator(all_time_low, 2)}"
                         }
                         p { style: "font-size:small;",
-                            "Max Purchase: {format_comma_seperator(max_buyable, 5)}"
+                            "Market Cap: ${format_compact(coin.market_cap())} | 24h Volume: ${format_compact(coin.volume_24h())}"
                         }
+
+                        h4 { style: "margin-top: 10px;", "Mining" }
                         p { style: "font-size:small;",
-                            "Coin Balance: {format_comma_seperator(coin_balance, 5)}"
+                            "Blocks: {coin.blocks} / {coin.max_blocks}"
                         }
-                        br {}
-                        p { style: "font-size: medium;", "Buy" }
-                        div {
-                            class: "market-buttons",
-                            style: "justify-content: space-between;margin-bottom: 10px;",
-                            button {
-                                class: "sell-btn market",
-                                disabled: !can_buy_amount(1.0),
-                                onclick: {
-                                    let do_buy = do_buy.clone();
-                                    move |_| {
-                                        do_buy(1.0, false);
-                                    }
-                                },
-                                "+1"
-                            }
-                            button {
-                                class: "sell-btn market",
-                                disabled: !can_buy_amount(10.0),
-                                onclick: {
-                                    let do_buy = do_buy.clone();
-                                    move |_| {
-                                        do_buy(10.0, false);
-                                    }
-                                },
-                                "+10"
-                            }
-                            button {
-                                class: "sell-btn market",
-                                disabled: !can_buy_amount(100.0),
-                                onclick: {
-                                    let do_buy = do_buy.clone();
-                                    move |_| {
-                                        do_buy(100.0, false);
-                                    }
-                                },
-                                "+100"
-                            }
-                            button {
-                                class: "sell-btn market",
-                                disabled: !can_buy_amount(max_buyable),
-                                onclick: {
-                                    let do_buy = do_buy.clone();
-                                    move |_| {
-                                        do_buy(max_buyable, true);
-                                    }
-                                },
-                                "Max"
-                            }
+                        DetailBar { percent: block_percent }
+                        p { style: "font-size:small;",
+                            "Next Share: ~{format_eta(coin.get_share_eta())} at {format_comma_seperator(coin.hashes_per_share, 0)} hashes per share"
                         }
-                        p { style: "font-size: medium;", "Sell" }
-                        div {
-                            class: "market-buttons",
-                            style: "justify-content: space-between;",
-                            button {
-                                class: "sell-btn market",
-                                disabled: !can_sell_amount(1.0),
-                                onclick: {
-                                    let do_sell = do_sell.clone();
-                                    move |_| {
-                                        do_sell(1.0, false);
-                                    }
-                                },
-                                "-1"
-                            }
-                            button {
-                                class: "sell-btn market",
-                                disabled: !can_sell_amount(10.0),
-                                onclick: {
-                                    let do_sell = do_sell.clone();
-                                    move |_| {
-                                        do_sell(10.0, false);
-                                    }
-                                },
-                                "-10"
-                            }
-                            button {
-                                class: "sell-btn market",
-                                disabled: !can_sell_amount(100.0),
-                                onclick: {
-                                    let do_sell = do_sell.clone();
-                                    move |_| {
-                                        do_sell(100.0, false);
-                                    }
-                                },
-                                "-100"
+                        DetailBar { percent: share_percent }
+                        p { style: "font-size:small;",
+                            "Next Block: ~{format_eta(coin.get_block_eta())} at {coin.shares_per_block} shares per block, {coin.shares} shares in"
+                        }
+                        DetailBar { percent: next_block_percent }
+                        p { style: "font-size:small;",
+                            "Luck: {discovery.share_luck() * 100.0:.0}% shares | {discovery.block_luck() * 100.0:.0}% blocks ({discovery.found_blocks} found)"
+                        }
+                        p { style: "font-size:small;",
+                            "Streaks: {discovery.lucky_streak} lucky blocks (best {discovery.best_lucky_streak}) | Fastest block: {fastest_block} | Longest drought: {longest_drought}"
+                        }
+                        p { style: "font-size:small;",
+                            "Latency: {format_comma_seperator(coin.get_stale_rate() * 100.0, 1)}% stale shares | {coin.stale_stats().orphaned_blocks} orphaned blocks"
+                        }
+                        p { style: "font-size:small;",
+                            "Difficulty: {format_comma_seperator(coin.get_difficulty(), 2)} | Network: {format_compact(coin.get_network_hash())} H/s"
+                        }
+                        p { style: "font-size:small;",
+                            "Effective Hash: {format_comma_seperator(coin.get_effective_hash(hash_rate), 2)} of {hash_rate} H/s"
+                        }
+                        p { style: "font-size:small;",
+                            "Block Reward: {format_comma_seperator(coin.get_block_reward(), 5)} | Halvings: {coin.get_halvings()}"
+                        }
+                        if !rivals.is_empty() {
+                            p { style: "font-size:small;",
+                                "Rival Miners: {rivals.join(\", \")} ({format_compact(coin.get_rival_hash())} H/s)"
                             }
-                            button {
-                                class: "sell-btn market",
-                                disabled: {
-                                    let coin_name = coin_name_can_sell_max.clone();
-                                    let mkt = MARKET().clone();
-                                    let coin = mkt.coin_by_name(&coin_name);
-                                    match coin {
-                                        Some(coin) => coin.balance <= 0.0,
-                                        None => true,
-                                    }
-                                },
-                                onclick: {
-                                    let do_sell = do_sell.clone();
-                                    move |_| {
-                                        do_sell(max_buyable, true);
-                                    }
-                                },
-                                "Max"
+                        }
+                        p { style: "font-size:small;",
+                            "Your Hash Share: {format_comma_seperator(coin.get_player_share() * 100.0, 1)}% of the network"
+                        }
+
+                        p { style: "font-size:small;",
+                            "NPC Flow: ${format_comma_seperator(npc_flow, 2)} last update"
+                        }
+                        h4 { style: "margin-top: 10px;", "Risk" }
+                        p { style: "font-size:small;",
+                            "Age: {coin.get_age()} days | Rug chance: {format_comma_seperator(coin.calculate_rug_chance() * 100.0, 3)}% per day"
+                        }
+
+                        h4 { style: "margin-top: 10px;", "Profit Factor" }
+                        if profit_series.len() > 1 {
+                            LineChart {
+                                padding_top: 20,
+                                padding_left: 70,
+                                padding_right: 20,
+                                padding_bottom: 20,
+                                height: "120px",
+                                series: vec![profit_series],
+                                labels: profit_labels,
+                                label_interpolation: (|v| format!("${}", format_compact(v as f64))) as fn(f32) -> String,
+                                show_dots: false,
+                                show_grid: false,
+                                line_width: "0.5%",
+                                max_ticks: 4
                             }
+                        } else {
+                            p { class: "coin-hint", "The trend fills in as the market updates." }
                         }
-                    }
-                    div {
-                        class: "flex flex-row",
-                        style: "justify-content: space-between;",
-                        button { class: "", onclick: close_modal, "Close" }
-                        button {
-                            class: "",
-                            disabled: {
-                                let coin_name = coin_name_replace.clone();
-                                let mkt = MARKET().clone();
-                                let coin = mkt.coin_by_name(&coin_name);
-                                if coin.is_some() {
-                                    let coin = coin.unwrap();
-                                    let new_coin_cooldown = MINING_RIG().get_new_coin_cooldown();
-                                    if new_coin_cooldown == 0 {
-                                        if coin.balance > 0.0 { true } else { false }
-                                    } else {
-                                        true
+
+                        h4 { style: "margin-top: 10px;", "Your Trades" }
+                        if let Some(position) = position {
+                            p { style: "font-size:small;",
+                                "Avg Cost: ${format_comma_seperator(position.avg_cost(), 2)} | Realized: ${format_comma_seperator(position.realized, 2)} | Unrealized: ${format_comma_seperator(position.unrealized(coin.current_price), 2)}"
+                            }
+                        }
+                        if trades.is_empty() {
+                            p { class: "coin-hint", "No trades for this coin yet." }
+                        } else {
+                            div {
+                                class: "sunken-panel",
+                                style: "height: 150px; overflow: auto;",
+                                table { class: "interactive w-full noselect",
+                                    thead {
+                                        tr {
+                                            th { "Day" }
+                                            th { "Type" }
+                                            th { "Amount" }
+                                            th { "Price" }
+                                            th { "Cash" }
+                                        }
                                     }
-                                } else {
-                                    true
-                                }
-                            },
-                            onclick: move |_| {
-                                let coin_name_replace = coin_name_replace.clone();
-                                let mut confirm_modal = confirm_modal.clone();
-                                async move {
-                                    let msg = format!(
-                                        "Are you sure you want to dismiss {}?\n\nThis action cannot be undone.",
-                                        coin_name_replace,
-                                    );
-                                    confirm_modal.write().msg = msg;
-                                    confirm_modal.write().show = true;
-                                    let confirm = loop {
-                                        let conf = confirm_modal().confirm;
-                                        match conf {
-                                            Some(confirm) => {
-                                                confirm_modal.write().confirm = None;
-                                                break confirm;
-                                            }
-                                            None => {
-                                                TimeoutFuture::new(100).await;
+                                    tbody {
+                                        for entry in trades.iter() {
+                                            tr {
+                                                td { style: "padding: 3px;", "{entry.day}" }
+                                                td { style: "padding: 3px;", "{entry.kind.label()}" }
+                                                td { style: "padding: 3px;font-family: 'Courier New', Courier, monospace;",
+                                                    "{format_comma_seperator(entry.amount, 5)}"
+                                                }
+                                                td { style: "padding: 3px;",
+                                                    "${format_comma_seperator(entry.price, 2)}"
+                                                }
+                                                td { style: pnl_style(entry.cash),
+                                                    "${format_comma_seperator(entry.cash, 2)}"
+                                                }
                                             }
-                                        };
-                                    };
-                                    if confirm {
-                                        let mut series_labels = series_labels.clone();
-                                        let mut series = series.clone();
-                                        let mut labels = labels.clone();
-                                        let rig_lvl = MINING_RIG().get_level();
-                                        let day = GAME_TIME().day;
-                                        let coin_name = coin_name_replace.clone();
-                                        let mkt = MARKET().clone();
-                                        let coin = mkt.coin_by_name(&coin_name);
-                                        let coin = match coin {
-                                            Some(coin) => coin,
-                                            None => return,
-                                        };
-                                        replace_coin(&coin, &mut series_labels, &mut series, rig_lvl, day);
-                                        MINING_RIG.write().set_new_coin_cooldown();
-                                        let latest_coin = MARKET().get_newest_coin();
-                                        if let Some(coin) = latest_coin {
-                                            run_sim_one_day_single(&mut series, &mut labels, &coin);
                                         }
-                                        let msg = format!("Dismissed {coin_name}");
-                                        spawn_local(async move {
-                                            command_line_output(&msg).await;
-                                        });
-                                        BUY_MODAL.write().show = false;
-                                        BUY_MODAL.write().coin = None;
-                                        DO_SAVE.write().save = true;
                                     }
                                 }
-                            },
-                            "Dismiss Coin"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+pub fn DetailBar(percent: f64) -> Element {
+    let width = percent.clamp(0.0, 100.0);
+
+    rsx! {
+        div { class: "progress-bar sunken-panel", style: "overflow: hidden;",
+            div { class: "progress", style: "width: {width}%;" }
+        }
+    }
+}
+
+#[component]
+pub fn PoolDashboard(coin_id: CoinId, index: usize) -> Element {
+    let coin = match MARKET().coin_by_id(coin_id) {
+        Some(coin) => coin.clone(),
+        None => return rsx! {},
+    };
+
+    let scheme = coin.get_pool_scheme();
+    let pool = coin
+        .pool
+        .clone()
+        .unwrap_or_else(|| pool::PoolState::new(scheme));
+    let luck = pool.luck() * 100.0;
+    let luck_style = if luck >= 100.0 {
+        "color: #070;"
+    } else {
+        "color: #a00;"
+    };
+
+    rsx! {
+        div { class: "sunken-panel", style: "padding: 6px;margin-top: 6px;",
+            div { class: "flex flex-row", style: "align-items: center;gap: 6px;",
+                label { r#for: "mining-pool-{index}", "Pool:" }
+                select {
+                    id: "mining-pool-{index}",
+                    class: "select",
+                    onchange: move |e| {
+                        let Some(scheme) = PoolScheme::all()
+                            .into_iter()
+                            .find(|s| s.label() == e.value()) else {
+                            return;
+                        };
+
+                        let forfeited = MARKET
+                            .write()
+                            .mut_coin_by_id(coin_id)
+                            .map(|c| c.join_pool(scheme))
+                            .unwrap_or(0.0);
+                        MARKET.write().set_profit_factor(&SELECTION());
+                        DO_SAVE.write().save = true;
+
+                        let mut msg = format!("Switched to {} for {}", scheme.label(), coin.name);
+                        if forfeited > 0.0 {
+                            msg.push_str(&format!(", forfeited {} pending", truncate_price(forfeited)));
+                        }
+                        spawn_local(async move {
+                            command_line_output(&msg).await;
+                        });
+                    },
+                    for option_scheme in PoolScheme::all() {
+                        option {
+                            value: "{option_scheme.label()}",
+                            selected: option_scheme == scheme,
+                            "{option_scheme.label()}"
                         }
                     }
                 }
+                span { class: "coin-hint", "Fee {scheme.fee() * 100.0:.1}%" }
+            }
+            p { class: "coin-hint", "{scheme.description()}" }
+            div { class: "flex flex-row", style: "justify-content: space-between;",
+                span { "Shares: {format_comma_seperator(pool.accepted_shares, 0)}" }
+                span { style: luck_style, "Luck: {luck:.0}%" }
+                span { "Blocks: {pool.blocks_found}" }
+            }
+            div { class: "flex flex-row", style: "justify-content: space-between;",
+                span { "Pending: {truncate_price(pool.pending())}" }
+                span { "Paid: {truncate_price(pool.paid)}" }
+                span { "Fees: {truncate_price(pool.fees)}" }
             }
         }
     }
@@ -4225,6 +6435,103 @@ pub fn Chart(
         calc_padding_labels(&labels, &mut padding_bottom);
     });
 
+    let mut indicator_coin: Signal<Option<CoinId>> = use_signal(|| None);
+    let mut indicators = use_signal(IndicatorSet::default);
+    let mut zoom = use_signal(|| ChartZoom::Day);
+
+    let focus = indicator_coin().and_then(|id| {
+        let mkt = MARKET();
+        mkt.coin_by_id(id).filter(|c| c.active).cloned()
+    });
+
+    // With a coin picked the chart shows only that coin and its overlays
+    let (chart_series, chart_series_labels, focus_prices) = match &focus {
+        Some(coin) => {
+            let prices = match zoom() {
+                ChartZoom::Day => match series().get(coin.index) {
+                    Some(prices) if !prices.is_empty() => prices.clone(),
+                    _ => coin.prices.clone(),
+                },
+                ChartZoom::Month => coin
+                    .candles(Some(MAX_DAILY_CANDLES))
+                    .iter()
+                    .map(|c| c.close)
+                    .collect(),
+                ChartZoom::Life => coin.candles(None).iter().map(|c| c.close).collect(),
+            };
+            let set = indicators();
+
+            let mut lines = vec![prices.clone()];
+            let mut names = vec![coin.name.clone()];
+
+            if set.sma {
+                lines.push(sma(&prices, SMA_PERIOD));
+                names.push(format!("SMA {SMA_PERIOD}"));
+            }
+            if set.ema {
+                lines.push(ema(&prices, EMA_PERIOD));
+                names.push(format!("EMA {EMA_PERIOD}"));
+            }
+            if set.bollinger {
+                let (upper, lower) = bollinger(&prices, BOLLINGER_PERIOD, BOLLINGER_WIDTH);
+                lines.push(upper);
+                names.push("BB Upper".to_string());
+                lines.push(lower);
+                names.push("BB Lower".to_string());
+            }
+
+            (lines, names, prices)
+        }
+        None => (series(), series_labels(), Vec::new()),
+    };
+
+    // Labels for the focused coin's history, which may be shorter than the
+    // full axis for a new coin
+    let focus_labels = match (&focus, zoom()) {
+        (Some(coin), ChartZoom::Month) => candle_labels(&coin.candles(Some(MAX_DAILY_CANDLES))),
+        (Some(coin), ChartZoom::Life) => candle_labels(&coin.candles(None)),
+        _ => {
+            let labels = labels();
+            let skip = labels.len().saturating_sub(focus_prices.len());
+            labels[skip..].to_vec()
+        }
+    };
+
+    let rsi_series = vec![
+        rsi(&focus_prices, RSI_PERIOD)
+            .into_iter()
+            .map(|v| v as f32)
+            .collect::<Vec<f32>>(),
+        vec![70.0; focus_prices.len()],
+        vec![30.0; focus_prices.len()],
+    ];
+
+    let volume_series = {
+        let volume = match &focus {
+            // Volume is only kept for the last day of updates
+            Some(_) if zoom() != ChartZoom::Day => Vec::new(),
+            Some(coin) => match &coin.supply {
+                Some(supply) => supply.volume.iter().map(|v| *v as f32).collect(),
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        vec![volume]
+    };
+
+    let toggle = move |pick: fn(&mut IndicatorSet)| {
+        move |_| {
+            let mut set = indicators();
+            pick(&mut set);
+            indicators.set(set);
+        }
+    };
+
+    let sentiment = MARKET().sentiment();
+    let sentiment_label = sentiment.label();
+    let sentiment_index = sentiment.index;
+    let sentiment_return = sentiment.last_return * 100.0;
+
     rsx! {
         div { class: "flex flex-col items-center justify-center",
             div { class: "aspect-w-1 aspect-h-1  overflow-hidden window h-fit",
@@ -4244,19 +6551,19 @@ pub fn Chart(
                 }
 
                 div { class: "window-body text-md status-bar-field",
-                    if series().iter().all(|s| s.len() > 0) {
+                    if chart_series.iter().all(|s| s.len() > 0) {
                         LineChart {
                             padding_top: 20,
                             padding_left: padding_left(),
                             padding_right: 100,
                             padding_bottom: padding_bottom(),
                             height: "250px",
-                            series: series().into_iter().map(|s| s.into_iter().map(|v| v as f32).collect()).collect(),
-                            labels: labels(),
+                            series: chart_series.into_iter().map(|s| s.into_iter().map(|v| v as f32).collect()).collect(),
+                            labels: if focus.is_some() { focus_labels.clone() } else { labels() },
                             label_interpolation: (|v| format!("${}", format_comma_seperator(v, 2))) as fn(f32) -> String,
-                            series_labels: series_labels(),
+                            series_labels: chart_series_labels,
                             show_labels: true,
-                            show_lines: false,
+                            show_lines: focus.is_some(),
                             show_dotted_grid: false,
                             show_grid: false,
                             line_width: "0.25%",
@@ -4264,12 +6571,131 @@ pub fn Chart(
                             max_ticks: 12
                         }
                     }
+                    if focus.is_some() && indicators().rsi && focus_prices.len() > 1 {
+                        LineChart {
+                            padding_top: 10,
+                            padding_left: padding_left(),
+                            padding_right: 100,
+                            padding_bottom: 10,
+                            height: "80px",
+                            viewbox_height: 150,
+                            series: rsi_series,
+                            series_labels: vec![format!("RSI {RSI_PERIOD}"), "70".to_string(), "30".to_string()],
+                            lowest: 0.0,
+                            highest: 100.0,
+                            show_labels: true,
+                            show_dots: false,
+                            show_grid: false,
+                            line_width: "0.5%",
+                            max_ticks: 3
+                        }
+                    }
+                    if focus.is_some() && indicators().volume && !volume_series[0].is_empty() {
+                        BarChart {
+                            padding_top: 10,
+                            padding_left: padding_left(),
+                            padding_right: 100,
+                            padding_bottom: 10,
+                            height: "80px",
+                            viewbox_height: 150,
+                            series: volume_series,
+                            label_interpolation: (|v| format!("${}", format_compact(v as f64))) as fn(f32) -> String,
+                            show_series_labels: false,
+                            show_grid: false,
+                            bar_width: "0.5%",
+                            max_ticks: 3
+                        }
+                    }
+                }
+
+                div {
+                    class: "flex flex-row",
+                    style: "align-items: center;gap: 8px;padding: 4px;flex-wrap: wrap;",
+                    select {
+                        class: "select",
+                        onchange: move |e| {
+                            indicator_coin.set(e.value().parse::<u64>().ok().map(CoinId));
+                        },
+                        option { value: "all", selected: focus.is_none(), "All Coins" }
+                        for coin in MARKET().index_sorted_coins(false) {
+                            option {
+                                value: "{coin.id()}",
+                                selected: Some(coin.id()) == focus.as_ref().map(|c| c.id()),
+                                "{coin.name}"
+                            }
+                        }
+                    }
+                    select {
+                        class: "select",
+                        disabled: focus.is_none(),
+                        onchange: move |e| {
+                            if let Some(z) = ChartZoom::all().into_iter().find(|z| z.label() == e.value()) {
+                                zoom.set(z);
+                            }
+                        },
+                        for z in ChartZoom::all() {
+                            option { value: "{z.label()}", selected: z == zoom(), "{z.label()}" }
+                        }
+                    }
+                    for (id , label , checked , pick) in [
+                        ("ind-sma", "SMA", indicators().sma, (|s: &mut IndicatorSet| s.sma = !s.sma) as fn(&mut IndicatorSet)),
+                        ("ind-ema", "EMA", indicators().ema, |s: &mut IndicatorSet| s.ema = !s.ema),
+                        ("ind-bb", "Bollinger", indicators().bollinger, |s: &mut IndicatorSet| s.bollinger = !s.bollinger),
+                        ("ind-rsi", "RSI", indicators().rsi, |s: &mut IndicatorSet| s.rsi = !s.rsi),
+                        ("ind-vol", "Volume", indicators().volume, |s: &mut IndicatorSet| s.volume = !s.volume),
+                    ] {
+                        div { class: "field-row",
+                            input {
+                                id: id,
+                                r#type: "checkbox",
+                                disabled: focus.is_none(),
+                                checked: checked,
+                                onchange: toggle(pick)
+                            }
+                            label { r#for: id, "{label}" }
+                        }
+                    }
+                }
+
+                div { class: "status-bar",
+                    p {
+                        class: "status-bar-field p-1 font-mono p-2",
+                        style: "padding:4px;",
+                        "Sentiment: {sentiment_label}"
+                    }
+                    p {
+                        class: "status-bar-field p-1 font-mono p-2",
+                        style: "padding:4px;",
+                        "Index: {sentiment_index:.2}"
+                    }
+                    p {
+                        class: "status-bar-field p-1 font-mono p-2",
+                        style: "padding:4px;",
+                        "Last Move: {sentiment_return:.2}%"
+                    }
                 }
             }
         }
     }
 }
 
+/// Day labels for candles, thinned out to about a dozen along the axis.
+fn candle_labels(candles: &[PriceCandle]) -> Vec<String> {
+    let step = (candles.len() / 12).max(1);
+
+    candles
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            if i % step == 0 {
+                format!("D{}", c.day)
+            } else {
+                String::new()
+            }
+        })
+        .collect()
+}
+
 fn format_game_time(game_time: &GameTime) -> String {
     let day = if game_time.day < 10 {
         format!("0{}", game_time.day)
@@ -4292,6 +6718,37 @@ fn format_game_time(game_time: &GameTime) -> String {
     format!("Day: {}, Time {}:{}", day, hour, minute)
 }
 
+/// Formats an expected wait in seconds, e.g. "45s", "12m 5s" or "3h 20m".
+fn format_eta(seconds: Option<f64>) -> String {
+    let seconds = match seconds {
+        Some(seconds) if seconds.is_finite() => seconds.round() as u64,
+        _ => return "never".to_string(),
+    };
+
+    match seconds {
+        0..=59 => format!("{}s", seconds),
+        60..=3599 => format!("{}m {}s", seconds / 60, seconds % 60),
+        _ => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+/// Short form for large figures like market caps, e.g. 1.25M.
+fn format_compact(v: f64) -> String {
+    let abs = v.abs();
+
+    if abs >= 1_000_000_000_000.0 {
+        format!("{:.2}T", v / 1_000_000_000_000.0)
+    } else if abs >= 1_000_000_000.0 {
+        format!("{:.2}B", v / 1_000_000_000.0)
+    } else if abs >= 1_000_000.0 {
+        format!("{:.2}M", v / 1_000_000.0)
+    } else if abs >= 1_000.0 {
+        format!("{:.2}K", v / 1_000.0)
+    } else {
+        format!("{v:.2}")
+    }
+}
+
 fn format_comma_seperator<T: std::fmt::Display + std::str::FromStr>(
     v: T,
     decimals: usize,
@@ -4300,6 +6757,11 @@ fn format_comma_seperator<T: std::fmt::Display + std::str::FromStr>(
 
     let mut final_val = String::new();
 
+    let (sign, val) = match val.strip_prefix('-') {
+        Some(val) => ("-", val.to_string()),
+        None => ("", val),
+    };
+
     let val_split = val.split('.').collect::<Vec<&str>>();
 
     let val_iter = val_split[0].chars().rev().enumerate();
@@ -4311,6 +6773,8 @@ fn format_comma_seperator<T: std::fmt::Display + std::str::FromStr>(
         final_val.insert(0, c);
     }
 
+    final_val.insert_str(0, sign);
+
     let result = if val_split.len() == 1 {
         final_val
     } else if val_split[1] == "00" {
@@ -4387,6 +6851,14 @@ async fn update_progess_bar(progress_id: &str, progress: f64) {
         .unwrap();
 }
 
+fn update_progress_text(progress_id: &str, text: &str) {
+    let document = window().document().expect("should have document");
+
+    if let Some(p_text) = document.get_element_by_id(&format!("{}-pbar-text", progress_id)) {
+        p_text.set_text_content(Some(text));
+    }
+}
+
 async fn toggle_autosave() {
     let save_details = GALAXY_SAVE_DETAILS().clone();
 
@@ -4448,7 +6920,7 @@ fn run_sim_one_day(series: &mut Signal<Vec<Vec<f64>>>, labels: &mut Signal<Vec<S
 
     {
         let mut current_labels = labels.write();
-        current_labels.push("|".to_string());
+        current_labels.push(GAME_TIME().chart_label());
         if current_labels.len() > MAX_SERIES_LENGTH {
             current_labels.remove(0);
         }
@@ -4463,11 +6935,11 @@ fn run_sim_one_day_single(
     let mut mkt = MARKET.write();
     mkt.simulate_day_single(coin);
 
-    {
+    if let Some(index) = mkt.series_slot(coin.id()) {
         let mut current_series = series.write();
-        let index = coin.index;
+        let price = mkt.get_coin_prince(coin);
 
-        current_series[index].push(coin.current_price);
+        current_series[index].push(price);
         if current_series[index].len() > MAX_SERIES_LENGTH {
             current_series[index].remove(0);
         }
@@ -4475,13 +6947,72 @@ fn run_sim_one_day_single(
 
     {
         let mut current_labels = labels.write();
-        current_labels.push("|".to_string());
+        current_labels.push(GAME_TIME().chart_label());
         if current_labels.len() > MAX_SERIES_LENGTH {
             current_labels.remove(0);
         }
     }
 }
 
+/// Re-ranks coins for the auto-miner when it is due and mines the best ones.
+fn run_auto_mining() {
+    if !MINING_RIG().get_auto_mining_active() {
+        return;
+    }
+
+    let level = MINING_RIG().get_auto_mining_level();
+
+    if !MINING_RIG.write().auto_miner_mut().tick(level) {
+        return;
+    }
+
+    let slots = auto_mining_slots(level, SELECTION().max_selectable);
+    let due_diligence = MINING_RIG().get_due_diligence_level();
+    let equal_hash = MINING_RIG().get_hash_rate() / slots as u64;
+    let best = MINING_RIG()
+        .auto_miner()
+        .rank(&MARKET().coins, due_diligence, slots, equal_hash);
+
+    if best.is_empty() {
+        return;
+    }
+
+    let mut current: Vec<CoinId> = SELECTION().selections.iter().map(|s| s.coin_id()).collect();
+    let mut ranked = best.clone();
+    current.sort_by_key(|id| id.0);
+    ranked.sort_by_key(|id| id.0);
+
+    if current == ranked {
+        return;
+    }
+
+    let mkt = MARKET();
+    let mut sel = SELECTION.write();
+    sel.retain_coins(&best);
+
+    for id in best.iter().filter(|id| !current.contains(id)) {
+        if let Some(coin) = mkt.coin_by_id(*id) {
+            sel.make_selection(coin, false);
+        }
+    }
+    sel.update_ui();
+    drop(sel);
+
+    MARKET.write().set_profit_factor(&SELECTION());
+
+    let names = best
+        .iter()
+        .filter_map(|id| mkt.coin_by_id(*id).map(|c| c.name.clone()))
+        .collect::<Vec<String>>()
+        .join(", ");
+    let msg = format!("Auto-mining switched to {names}");
+    spawn_local(async move {
+        command_line_output(&msg).await;
+    });
+
+    DO_SAVE.write().save = true;
+}
+
 async fn do_mining() {
     let mut sel = SELECTION.write().clone();
 
@@ -4494,14 +7025,12 @@ async fn do_mining() {
     let mut mkt = MARKET.write();
 
     for selection in selected_coins.iter() {
-        let c_name = selection.clone().name;
-
-        let coin = mkt.coin_by_name(&c_name);
+        let coin = mkt.coin_by_id(selection.coin_id());
 
         match coin {
             Some(coin) => {
                 if !coin.active {
-                    sel.unmake_selection(coin.index);
+                    sel.unmake_selection(coin.id());
                     sel.update_ui();
 
                     DO_SAVE.write().save = true;
@@ -4537,7 +7066,7 @@ async fn do_mining() {
             if refill_time == 0 {
                 let auto_fill_cost = MINING_RIG().get_auto_power_fill_cost(GAME_TIME().day);
 
-                if mkt.bank.withdraw(auto_fill_cost) {
+                if mkt.spend(auto_fill_cost, LedgerKind::PowerRefill, "Auto-power fill") {
                     let fill_amount = MINING_RIG().get_auto_power_fill_amount();
                     MINING_RIG.write().fill_to_percent(fill_amount);
                     let power_available = MINING_RIG().get_power_fill();
@@ -4563,49 +7092,56 @@ async fn do_mining() {
     }
 
     let coin_selections = sel.get_selected();
-
-    let mineable = coin_selections
-        .iter()
-        .filter(|c| {
-            let coin = mkt.coin_by_name(&c.name);
-            match coin {
-                Some(coin) => coin.active && coin.blocks < coin.max_blocks,
-                None => false,
-            }
-        })
-        .count()
-        .max(1);
+    let allocation = sel.hash_allocation(&mkt.coins, hash_rate);
 
     for selection in coin_selections.iter() {
-        let c_name = selection.clone().name;
         let c_index = selection.clone().selection_index;
 
-        let coin = mkt.mut_coin_by_name(&c_name);
+        let coin = mkt.mut_coin_by_id(selection.coin_id());
 
         match coin {
             Some(coin) => {
                 if coin.active {
-                    coin.hash_coin(hash_rate / mineable as u64);
-
-                    let share_progress = coin.get_share_progress() as f64;
-                    let block_progress = coin.get_block_progress() as f64;
+                    let c_name = coin.name.clone();
+                    let coin_hash = allocation
+                        .iter()
+                        .find(|(id, _)| *id == coin.id())
+                        .map(|(_, hash)| *hash)
+                        .unwrap_or(0);
+                    let (share_coins, bonus_coins) = coin.hash_coin(coin_hash);
+
+                    let share_effort = coin.get_share_effort().min(1.0);
+                    let block_effort = coin.get_block_effort().min(1.0);
+                    let share_eta = format_eta(coin.get_share_eta());
+                    let block_eta = format_eta(coin.get_block_eta());
 
                     let c_index = c_index.clone();
 
                     update_progess_bar(
                         &format!("share-progress-{}", c_index.clone()),
-                        share_progress * 100.0,
+                        share_effort * 100.0,
                     )
                     .await;
+                    update_progress_text(
+                        &format!("share-progress-{}", c_index.clone()),
+                        &format!("~{} per share", share_eta),
+                    );
                     update_progess_bar(
                         &format!("block-progress-{}", c_index.clone()),
-                        block_progress * 100.0,
+                        block_effort * 100.0,
                     )
                     .await;
+                    update_progress_text(
+                        &format!("block-progress-{}", c_index.clone()),
+                        &format!("~{} per block", block_eta),
+                    );
+
+                    let (c_id, price) = (coin.id(), coin.current_price);
+                    mkt.record_mined(c_id, &c_name, price, share_coins, bonus_coins);
                 }
             }
             None => {
-                sel.unmake_selection(selection.index);
+                sel.unmake_selection(selection.coin_id());
                 sel.update_ui();
                 // update_progess_bar("share-progress", 0.0).await;
                 // update_progess_bar("block-progress", 0.0).await;
@@ -4627,7 +7163,10 @@ async fn do_fill_power() {
     let day = GAME_TIME().day;
     let power_cost = MINING_RIG().get_power_fill_cost(day);
 
-    if MARKET.write().bank.withdraw(power_cost) {
+    if MARKET
+        .write()
+        .spend(power_cost, LedgerKind::PowerRefill, "Fill Power")
+    {
         MINING_RIG.write().fill_power();
     }
 
@@ -4655,7 +7194,7 @@ async fn game_loop(
         let rig_lvl = MINING_RIG().level;
 
         for i in 0..10 {
-            let coin = gen_random_coin_with_set_index(i, rig_lvl);
+            let coin = gen_random_coin_with_set_index(i, rig_lvl, &mkt.taken_names());
 
             mkt.add_coin(coin.clone());
             series_labels.write().push(coin.name.clone());
@@ -4668,7 +7207,7 @@ async fn game_loop(
         *MARKET.write() = mkt;
 
         run_sim_one_day(series, labels);
-        MARKET.write().set_profit_factor(1);
+        MARKET.write().set_profit_factor(&SELECTION());
 
         let seen_welcome = get_seen_welcome().await.unwrap_or_else(|_| false);
         if !seen_welcome {
@@ -4719,27 +7258,12 @@ async fn game_loop(
             let rig_lvl = MINING_RIG().level;
             let day = GAME_TIME().day;
             cull_market(series_labels, series, rig_lvl, day.clone());
+            run_forks(series_labels, series, day);
             run_sim_one_day(series, labels);
             MARKET.write().run_rug_pull(day.clone());
-
-            let sel = SELECTION().clone();
-            let coin_selections = sel.get_selected();
-
-            let mineable = coin_selections
-                .iter()
-                .filter(|c| {
-                    let mkt = MARKET().clone();
-
-                    let coin = mkt.coin_by_name(&c.name);
-                    match coin {
-                        Some(coin) => coin.active && coin.blocks < coin.max_blocks,
-                        None => false,
-                    }
-                })
-                .count()
-                .max(1);
-
-            MARKET.write().set_profit_factor(mineable);
+            MARKET.write().record_net_worth(day, NFT_STUDIO().value());
+            MARKET.write().set_profit_factor(&SELECTION());
+            run_auto_mining();
 
             iter = 0;
         }
@@ -4754,7 +7278,12 @@ async fn game_loop(
 
         let amount_per_tick = NFT_STUDIO().money_per_tick();
 
-        MARKET.write().bank.deposit(amount_per_tick);
+        // Folds into one ledger entry per day
+        if amount_per_tick > 0.0 {
+            MARKET
+                .write()
+                .earn(amount_per_tick, LedgerKind::NftSale, "Studio income");
+        }
 
         ticks_per_second.write().tick();
         let popularity = NFT_STUDIO.write().decriment_popularity(GAME_TIME().day);
@@ -5056,18 +7585,27 @@ async fn recover_game_state(
         game_state.market.reverse_price_history();
     }
 
+    if game_state.version.unwrap_or(0) < 2 {
+        game_state.market.migrate_coin_ids();
+
+        if let Some(selection_multi) = game_state.selection_multi.as_mut() {
+            selection_multi.migrate_coin_ids(&game_state.market.coins);
+        }
+    }
+
     match game_state.selection {
         Some(selection) => {
             let sel_name = selection.name.clone();
-            let sel_index = selection.index;
 
             let mut sel_multi = match game_state.selection_multi.clone() {
                 Some(sel_multi) => sel_multi,
                 None => SelectionMultiList::new(),
             };
 
-            if sel_name.is_some() && sel_index.is_some() {
-                sel_multi.make_selection(sel_index.unwrap(), &sel_name.unwrap(), false);
+            if let Some(sel_name) = sel_name {
+                if let Some(coin) = game_state.market.coin_by_name(&sel_name) {
+                    sel_multi.make_selection(coin, false);
+                }
             }
 
             game_state.selection = None;
@@ -5075,7 +7613,7 @@ async fn recover_game_state(
         None => {}
     }
 
-    let market_chart_data = game_state.market.get_chart();
+    let market_chart_data = game_state.market.get_chart(&game_state.game_time);
 
     let nft_studio = match game_state.nft_studio {
         Some(nft_studio) => nft_studio,
@@ -5197,7 +7735,7 @@ async fn save_game_state() {
         selection: None,
         mining_rig: MINING_RIG.read().clone(),
         galaxy_save_details: GALAXY_SAVE_DETAILS.read().clone(),
-        version: Some(1),
+        version: Some(2),
         nft_studio: Some(NFT_STUDIO().clone()),
         selection_multi: Some(SELECTION().clone()),
     };
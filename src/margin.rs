@@ -0,0 +1,377 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::crypto_coin::CoinId;
+
+/// Share of a short's notional value posted as collateral when it is opened.
+pub const SHORT_INITIAL_MARGIN: f64 = 0.5;
+/// Positions are liquidated once equity falls below this share of notional.
+pub const MAINTENANCE_MARGIN: f64 = 0.25;
+/// Charged on the borrowed value once per game day.
+pub const DAILY_INTEREST: f64 = 0.003;
+pub const LEVERAGE_OPTIONS: [f64; 2] = [2.0, 3.0];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MarginKind {
+    Short,
+    Long,
+}
+
+impl MarginKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MarginKind::Short => "Short",
+            MarginKind::Long => "Long",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarginPosition {
+    pub coin: CoinId,
+    pub coin_name: String,
+    pub kind: MarginKind,
+    pub amount: f64,
+    pub entry_price: f64,
+    pub collateral: f64,
+    pub borrowed: f64,
+    pub opened_day: u64,
+}
+
+impl MarginPosition {
+    /// The loan on a long covers whatever the collateral does not.
+    pub fn new(
+        kind: MarginKind,
+        coin: CoinId,
+        coin_name: &str,
+        amount: f64,
+        price: f64,
+        collateral: f64,
+        day: u64,
+    ) -> Self {
+        let borrowed = match kind {
+            MarginKind::Short => 0.0,
+            MarginKind::Long => amount * price - collateral,
+        };
+
+        MarginPosition {
+            coin,
+            coin_name: coin_name.to_string(),
+            kind,
+            amount,
+            entry_price: price,
+            collateral,
+            borrowed,
+            opened_day: day,
+        }
+    }
+
+    pub fn notional(&self, price: f64) -> f64 {
+        self.amount * price
+    }
+
+    pub fn equity(&self, price: f64) -> f64 {
+        match self.kind {
+            MarginKind::Short => self.collateral + (self.entry_price - price) * self.amount,
+            MarginKind::Long => self.amount * price - self.borrowed,
+        }
+    }
+
+    /// Profit against the cash put up when the position was opened. Short
+    /// interest is taken out of collateral so it is not counted here.
+    pub fn pnl(&self, price: f64) -> f64 {
+        match self.kind {
+            MarginKind::Short => (self.entry_price - price) * self.amount,
+            MarginKind::Long => self.equity(price) - self.collateral,
+        }
+    }
+
+    /// The price at which equity drops to the maintenance margin.
+    pub fn liquidation_price(&self) -> f64 {
+        if self.amount <= 0.0 {
+            return 0.0;
+        }
+
+        match self.kind {
+            MarginKind::Short => {
+                (self.collateral + self.entry_price * self.amount)
+                    / ((1.0 + MAINTENANCE_MARGIN) * self.amount)
+            }
+            MarginKind::Long => self.borrowed / ((1.0 - MAINTENANCE_MARGIN) * self.amount),
+        }
+    }
+
+    pub fn should_liquidate(&self, price: f64) -> bool {
+        self.equity(price) < MAINTENANCE_MARGIN * self.notional(price)
+    }
+
+    fn interest(&self, price: f64) -> f64 {
+        match self.kind {
+            MarginKind::Short => self.notional(price) * DAILY_INTEREST,
+            MarginKind::Long => self.borrowed * DAILY_INTEREST,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MarginAccount {
+    pub positions: Vec<MarginPosition>,
+    pub last_interest_day: Option<u64>,
+}
+
+impl MarginAccount {
+    pub fn new() -> Self {
+        MarginAccount {
+            positions: Vec::new(),
+            last_interest_day: None,
+        }
+    }
+
+    /// Cash the player has to put up to open the position.
+    pub fn required_collateral(kind: MarginKind, amount: f64, price: f64, leverage: f64) -> f64 {
+        match kind {
+            MarginKind::Short => amount * price * SHORT_INITIAL_MARGIN,
+            MarginKind::Long => amount * price / leverage.max(1.0),
+        }
+    }
+
+    /// Opens a position or adds to the existing one for the same coin and
+    /// kind, averaging the entry price.
+    pub fn open(&mut self, position: MarginPosition) {
+        if let Some(existing) = self.position_mut(position.coin, position.kind) {
+            let total = existing.amount + position.amount;
+            existing.entry_price = (existing.entry_price * existing.amount
+                + position.entry_price * position.amount)
+                / total;
+            existing.amount = total;
+            existing.collateral += position.collateral;
+            existing.borrowed += position.borrowed;
+            return;
+        }
+
+        self.positions.push(position);
+    }
+
+    pub fn position(&self, coin: CoinId, kind: MarginKind) -> Option<&MarginPosition> {
+        self.positions
+            .iter()
+            .find(|p| p.coin == coin && p.kind == kind)
+    }
+
+    fn position_mut(&mut self, coin: CoinId, kind: MarginKind) -> Option<&mut MarginPosition> {
+        self.positions
+            .iter_mut()
+            .find(|p| p.coin == coin && p.kind == kind)
+    }
+
+    pub fn positions_for(&self, coin: CoinId) -> Vec<MarginPosition> {
+        self.positions
+            .iter()
+            .filter(|p| p.coin == coin)
+            .cloned()
+            .collect()
+    }
+
+    /// Closes the position at the given price, returning it together with
+    /// the cash handed back to the player.
+    pub fn close(
+        &mut self,
+        coin: CoinId,
+        kind: MarginKind,
+        price: f64,
+    ) -> Option<(MarginPosition, f64)> {
+        let index = self
+            .positions
+            .iter()
+            .position(|p| p.coin == coin && p.kind == kind)?;
+        let position = self.positions.remove(index);
+        let returned = position.equity(price).max(0.0);
+
+        Some((position, returned))
+    }
+
+    /// Closes every position on the coin, used when it is rugged or leaves
+    /// the market.
+    pub fn close_all_for(&mut self, coin: CoinId, price: f64) -> Vec<(MarginPosition, f64)> {
+        let mut closed = Vec::new();
+
+        for kind in [MarginKind::Short, MarginKind::Long] {
+            if let Some(result) = self.close(coin, kind, price) {
+                closed.push(result);
+            }
+        }
+
+        closed
+    }
+
+    /// Closes every position under its maintenance margin. `price_of` returns
+    /// the current price of a coin.
+    pub fn liquidate<F: Fn(CoinId) -> Option<f64>>(
+        &mut self,
+        price_of: F,
+    ) -> Vec<(MarginPosition, f64)> {
+        let mut liquidated = Vec::new();
+
+        self.positions.retain(|p| match price_of(p.coin) {
+            Some(price) if p.should_liquidate(price) => {
+                liquidated.push((p.clone(), p.equity(price).max(0.0)));
+                false
+            }
+            _ => true,
+        });
+
+        liquidated
+    }
+
+    /// Charges interest on every position for each day passed since the
+    /// last charge. Returns the total charged.
+    pub fn charge_interest<F: Fn(CoinId) -> Option<f64>>(&mut self, day: u64, price_of: F) -> f64 {
+        let last_day = *self.last_interest_day.get_or_insert(day);
+
+        if day <= last_day {
+            return 0.0;
+        }
+
+        let days = (day - last_day) as f64;
+        self.last_interest_day = Some(day);
+
+        let mut total = 0.0;
+
+        for position in self.positions.iter_mut() {
+            if let Some(price) = price_of(position.coin) {
+                let interest = position.interest(price) * days;
+
+                // Shorts pay out of their collateral, longs add it to the loan
+                match position.kind {
+                    MarginKind::Short => position.collateral -= interest,
+                    MarginKind::Long => position.borrowed += interest,
+                }
+
+                total += interest;
+            }
+        }
+
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn short() -> MarginPosition {
+        let collateral = MarginAccount::required_collateral(MarginKind::Short, 10.0, 100.0, 1.0);
+        MarginPosition::new(
+            MarginKind::Short,
+            CoinId(1),
+            "Test",
+            10.0,
+            100.0,
+            collateral,
+            0,
+        )
+    }
+
+    fn long() -> MarginPosition {
+        let collateral = MarginAccount::required_collateral(MarginKind::Long, 10.0, 100.0, 2.0);
+        MarginPosition::new(
+            MarginKind::Long,
+            CoinId(1),
+            "Test",
+            10.0,
+            100.0,
+            collateral,
+            0,
+        )
+    }
+
+    #[test]
+    fn short_liquidates_past_its_liquidation_price() {
+        let position = short();
+        let price = position.liquidation_price();
+
+        assert!(close(position.collateral, 500.0));
+        assert!(close(price, 120.0));
+        assert!(close(
+            position.equity(price),
+            MAINTENANCE_MARGIN * position.notional(price)
+        ));
+        assert!(!position.should_liquidate(price - 0.01));
+        assert!(position.should_liquidate(price + 0.01));
+        assert!(close(position.pnl(90.0), 100.0));
+    }
+
+    #[test]
+    fn long_liquidates_below_its_liquidation_price() {
+        let position = long();
+        let price = position.liquidation_price();
+
+        assert!(close(position.borrowed, 500.0));
+        assert!(close(price, 500.0 / 7.5));
+        assert!(close(
+            position.equity(price),
+            MAINTENANCE_MARGIN * position.notional(price)
+        ));
+        assert!(!position.should_liquidate(price + 0.01));
+        assert!(position.should_liquidate(price - 0.01));
+        assert!(close(position.pnl(110.0), 100.0));
+    }
+
+    #[test]
+    fn liquidate_only_takes_positions_under_margin() {
+        let mut account = MarginAccount::new();
+        account.open(short());
+        account.open(long());
+
+        let liquidated = account.liquidate(|_| Some(130.0));
+
+        assert_eq!(liquidated.len(), 1);
+        assert_eq!(liquidated[0].0.kind, MarginKind::Short);
+        assert!(close(liquidated[0].1, 200.0));
+        assert!(account.position(CoinId(1), MarginKind::Long).is_some());
+    }
+
+    #[test]
+    fn adding_to_a_position_averages_the_entry() {
+        let mut account = MarginAccount::new();
+        account.open(long());
+        account.open(MarginPosition::new(
+            MarginKind::Long,
+            CoinId(1),
+            "Test",
+            10.0,
+            200.0,
+            1000.0,
+            1,
+        ));
+
+        let position = account.position(CoinId(1), MarginKind::Long).unwrap();
+        assert!(close(position.amount, 20.0));
+        assert!(close(position.entry_price, 150.0));
+        assert!(close(position.borrowed, 1500.0));
+    }
+
+    #[test]
+    fn interest_is_charged_per_day_passed() {
+        let mut account = MarginAccount::new();
+        account.open(short());
+        account.open(long());
+
+        assert_eq!(account.charge_interest(3, |_| Some(100.0)), 0.0);
+
+        let charged = account.charge_interest(5, |_| Some(100.0));
+        let short = account.position(CoinId(1), MarginKind::Short).unwrap();
+        let long = account.position(CoinId(1), MarginKind::Long).unwrap();
+
+        assert!(close(charged, 2.0 * (1000.0 + 500.0) * DAILY_INTEREST));
+        assert!(close(
+            short.collateral,
+            500.0 - 2.0 * 1000.0 * DAILY_INTEREST
+        ));
+        assert!(close(long.borrowed, 500.0 + 2.0 * 500.0 * DAILY_INTEREST));
+    }
+}
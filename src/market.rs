@@ -4,10 +4,26 @@ use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen_futures::spawn_local;
 
-use crate::crypto_coin::CryptoCoin;
+use crate::alerts::AlertCenter;
+use crate::archetype::CoinArchetype;
+use crate::coin_identity::CoinIdentity;
+use crate::crypto_coin::{CoinId, CryptoCoin};
+use crate::fork::{can_fork, split, FORK_CHANCE, MAX_MARKET_SLOTS};
 use crate::i_db::SelectionMultiList;
+use crate::ico::{IcoOffering, IcoOutcome, IcoPipeline};
+use crate::ledger::{Ledger, LedgerKind};
+use crate::margin::{MarginAccount, MarginKind, MarginPosition};
 use crate::mining_rig::{Bank, MINING_RIG};
-use crate::utils::{command_line_output, rand_from_range, truncate_price, GameTime};
+use crate::net_worth::{NetWorthHistory, NetWorthSnapshot};
+use crate::news::NewsFeed;
+use crate::npc::{price_impact, NpcDesk};
+use crate::sentiment::MarketSentiment;
+use crate::staking::StakeTerm;
+use crate::supply::CoinSort;
+use crate::swap::SwapQuote;
+use crate::utils::{
+    browser_notification, command_line_output, rand_from_range, truncate_price, GameTime,
+};
 
 pub const MAX_SERIES_LENGTH: usize = 96;
 pub static MARKET: GlobalSignal<Market> = Signal::global(|| Market::new());
@@ -28,6 +44,14 @@ pub struct Market {
     pub inactive_coins: Vec<CryptoCoin>,
     pub index: u64,
     pub bank: Bank,
+    pub ledger: Option<Ledger>,
+    pub sentiment: Option<MarketSentiment>,
+    pub news: Option<NewsFeed>,
+    pub margin: Option<MarginAccount>,
+    pub ico: Option<IcoPipeline>,
+    pub net_worth: Option<NetWorthHistory>,
+    pub alerts: Option<AlertCenter>,
+    pub npcs: Option<NpcDesk>,
 }
 
 impl Market {
@@ -39,16 +63,404 @@ impl Market {
             inactive_coins,
             index: 0,
             bank: Bank::new(),
+            ledger: Some(Ledger::new()),
+            sentiment: Some(MarketSentiment::new()),
+            news: Some(NewsFeed::new()),
+            margin: Some(MarginAccount::new()),
+            ico: Some(IcoPipeline::new()),
+            net_worth: Some(NetWorthHistory::new()),
+            alerts: Some(AlertCenter::new()),
+            npcs: Some(NpcDesk::new()),
         }
     }
 
+    pub fn ledger(&self) -> Ledger {
+        self.ledger.clone().unwrap_or_default()
+    }
+
+    pub fn ledger_mut(&mut self) -> &mut Ledger {
+        self.ledger.get_or_insert_with(Ledger::new)
+    }
+
+    pub fn sentiment(&self) -> MarketSentiment {
+        self.sentiment.clone().unwrap_or_default()
+    }
+
+    pub fn sentiment_mut(&mut self) -> &mut MarketSentiment {
+        self.sentiment.get_or_insert_with(MarketSentiment::new)
+    }
+
+    pub fn news(&self) -> NewsFeed {
+        self.news.clone().unwrap_or_default()
+    }
+
+    pub fn news_mut(&mut self) -> &mut NewsFeed {
+        self.news.get_or_insert_with(NewsFeed::new)
+    }
+
+    pub fn margin(&self) -> MarginAccount {
+        self.margin.clone().unwrap_or_default()
+    }
+
+    pub fn margin_mut(&mut self) -> &mut MarginAccount {
+        self.margin.get_or_insert_with(MarginAccount::new)
+    }
+
+    pub fn ico(&self) -> IcoPipeline {
+        self.ico.clone().unwrap_or_default()
+    }
+
+    pub fn ico_mut(&mut self) -> &mut IcoPipeline {
+        self.ico.get_or_insert_with(IcoPipeline::new)
+    }
+
+    pub fn alerts(&self) -> AlertCenter {
+        self.alerts.clone().unwrap_or_default()
+    }
+
+    pub fn alerts_mut(&mut self) -> &mut AlertCenter {
+        self.alerts.get_or_insert_with(AlertCenter::new)
+    }
+
+    pub fn npcs(&self) -> NpcDesk {
+        self.npcs.clone().unwrap_or_default()
+    }
+
+    pub fn npcs_mut(&mut self) -> &mut NpcDesk {
+        self.npcs.get_or_insert_with(NpcDesk::new)
+    }
+
+    /// Checks the player's alerts against the latest prices.
+    fn run_alerts(&mut self) {
+        let day = GAME_TIME().day;
+        let due_diligence = MINING_RIG().get_due_diligence_level();
+        let coins = self.coins.clone();
+
+        let alerts = self.alerts_mut();
+        let fired = alerts.check(&coins, due_diligence, day);
+        let use_browser = alerts.browser_notifications;
+
+        for msg in fired {
+            if use_browser {
+                browser_notification(&msg);
+            }
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+    }
+
+    pub fn net_worth(&self) -> NetWorthHistory {
+        self.net_worth.clone().unwrap_or_default()
+    }
+
+    pub fn net_worth_mut(&mut self) -> &mut NetWorthHistory {
+        self.net_worth.get_or_insert_with(NetWorthHistory::new)
+    }
+
+    /// Values everything the player owns at current prices. The NFT studio
+    /// is valued by the caller since it lives outside the market.
+    pub fn net_worth_snapshot(&self, day: u64, nfts: f64) -> NetWorthSnapshot {
+        let active = self.coins.iter().filter(|c| c.active);
+
+        let coins = active.clone().map(|c| c.balance * c.current_price).sum();
+        let staked = active.map(|c| c.staked_amount() * c.current_price).sum();
+
+        let margin = self
+            .margin()
+            .positions
+            .iter()
+            .map(|p| match self.coin_by_id(p.coin) {
+                Some(coin) => p.equity(coin.current_price).max(0.0),
+                None => 0.0,
+            })
+            .sum();
+
+        let presale = self
+            .ico()
+            .offerings
+            .iter()
+            .map(|o| o.purchased * o.presale_price)
+            .sum();
+
+        NetWorthSnapshot {
+            day,
+            bank: self.bank.balance,
+            coins,
+            staked,
+            margin,
+            presale,
+            nfts,
+        }
+    }
+
+    pub fn record_net_worth(&mut self, day: u64, nfts: f64) {
+        let snapshot = self.net_worth_snapshot(day, nfts);
+        self.net_worth_mut().record(snapshot);
+    }
+
+    /// Market value of held and staked coins, per active coin, largest first.
+    pub fn allocation(&self) -> Vec<(CryptoCoin, f64)> {
+        let mut allocation: Vec<(CryptoCoin, f64)> = self
+            .coins
+            .iter()
+            .filter(|c| c.active)
+            .map(|c| (c.clone(), (c.balance + c.staked_amount()) * c.current_price))
+            .filter(|(_, value)| *value > 0.0)
+            .collect();
+
+        allocation.sort_by(|a, b| b.1.total_cmp(&a.1));
+        allocation
+    }
+
+    /// Puts a generated coin up for presale, reserving its id.
+    pub fn announce_ico(&mut self, coin: CryptoCoin, day: u64) {
+        let offering = IcoOffering::new(coin, day);
+
+        let msg = format!(
+            "ICO: {} ({}) presale at ${}, lists from day {}",
+            offering.coin.name,
+            offering.coin.get_ticker(),
+            truncate_price(offering.presale_price),
+            offering.listing_day
+        );
+        spawn_local(async move {
+            command_line_output(&msg).await;
+        });
+
+        self.index += 1;
+        self.ico_mut().announce(offering);
+    }
+
+    /// Buys into a presale, the coins are credited once the coin lists.
+    pub fn buy_presale(&mut self, id: CoinId, amount: f64) -> bool {
+        let day = GAME_TIME().day;
+
+        let (name, price) = match self.ico().offering(id) {
+            Some(offering) if amount > 0.0 && amount <= offering.remaining_allocation() => {
+                (offering.coin.name.clone(), offering.presale_price)
+            }
+            _ => return false,
+        };
+
+        if !self.bank.withdraw(amount * price) {
+            return false;
+        }
+
+        self.ledger_mut().record_buy(day, id, &name, amount, price);
+
+        if let Some(offering) = self.ico_mut().offering_mut(id) {
+            offering.purchased += amount;
+            offering.coin.balance += amount;
+        }
+
+        true
+    }
+
+    /// Lists the next ready ICO coin into the slot, announcing how it went.
+    fn list_ico(&mut self, index: usize, day: u64) -> Option<CryptoCoin> {
+        let offering = self.ico_mut().take_ready(day)?;
+        let presale_price = offering.presale_price;
+        let (coin, outcome) = offering.list(index, day);
+
+        let msg = format!(
+            "ICO: {} {} at ${} (presale ${})",
+            coin.name,
+            outcome.label(),
+            truncate_price(coin.current_price),
+            truncate_price(presale_price)
+        );
+        spawn_local(async move {
+            command_line_output(&msg).await;
+        });
+
+        if outcome == IcoOutcome::Rug {
+            let msg = format!("{} developers vanished after the ICO", coin.name);
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+
+        Some(coin)
+    }
+
+    /// Opens (or adds to) a short or leveraged long, taking the collateral
+    /// from the bank.
+    pub fn open_margin(
+        &mut self,
+        coin: &CryptoCoin,
+        kind: MarginKind,
+        amount: f64,
+        leverage: f64,
+    ) -> bool {
+        let coin = match self.coin_by_id(coin.id()) {
+            Some(coin) if coin.active && amount > 0.0 => coin.clone(),
+            _ => return false,
+        };
+
+        let price = coin.current_price;
+        let collateral = MarginAccount::required_collateral(kind, amount, price, leverage);
+        let note = format!("{} {}", kind.label(), coin.name);
+
+        if !self.spend(collateral, LedgerKind::Margin, &note) {
+            return false;
+        }
+
+        let day = GAME_TIME().day;
+        let position =
+            MarginPosition::new(kind, coin.id(), &coin.name, amount, price, collateral, day);
+        self.margin_mut().open(position);
+
+        true
+    }
+
+    /// Closes a position at the current price, returning its profit.
+    pub fn close_margin(&mut self, id: CoinId, kind: MarginKind) -> Option<f64> {
+        let price = self.coin_by_id(id)?.current_price;
+        let (position, returned) = self.margin_mut().close(id, kind, price)?;
+
+        let note = format!("Close {} {}", kind.label(), position.coin_name);
+        self.earn(returned, LedgerKind::Margin, &note);
+
+        Some(position.pnl(price))
+    }
+
+    pub fn stake_coin(&mut self, id: CoinId, amount: f64, term: StakeTerm) -> bool {
+        let day = GAME_TIME().day;
+
+        match self.mut_coin_by_id(id) {
+            Some(coin) => coin.stake_coins(amount, term, day),
+            None => false,
+        }
+    }
+
+    /// Unstakes the coin, returning (returned, penalty).
+    pub fn unstake_coin(&mut self, id: CoinId) -> Option<(f64, f64)> {
+        let day = GAME_TIME().day;
+        let coin = self.mut_coin_by_id(id)?;
+        let (returned, penalty) = coin.unstake(day)?;
+
+        let name = coin.name.clone();
+        let price = coin.current_price;
+        self.ledger_mut()
+            .record_forfeit(day, id, &name, LedgerKind::Staking, penalty, price);
+
+        Some((returned, penalty))
+    }
+
+    /// Pays staking yield and returns matured stakes.
+    fn run_staking(&mut self) {
+        let day = GAME_TIME().day;
+        let ledger = self.ledger.get_or_insert_with(Ledger::new);
+
+        for coin in self.coins.iter_mut().filter(|c| c.active) {
+            let (earned, matured) = coin.accrue_stake(day);
+
+            ledger.record_mined(
+                day,
+                coin.id(),
+                &coin.name,
+                LedgerKind::Staking,
+                earned,
+                coin.current_price,
+            );
+
+            if matured {
+                let msg = format!("{} stake matured and returned to balance", coin.name);
+                spawn_local(async move {
+                    command_line_output(&msg).await;
+                });
+            }
+        }
+    }
+
+    fn settle_closed_margin(&mut self, closed: Vec<(MarginPosition, f64)>, reason: &str) {
+        for (position, returned) in closed {
+            let note = format!(
+                "{} {} {}",
+                reason,
+                position.kind.label(),
+                position.coin_name
+            );
+            self.earn(returned, LedgerKind::Margin, &note);
+
+            let msg = format!(
+                "{} {} on {} settled, ${} returned",
+                reason,
+                position.kind.label(),
+                position.coin_name,
+                truncate_price(returned)
+            );
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+    }
+
+    /// Charges daily interest and liquidates positions under their
+    /// maintenance margin.
+    fn run_margin(&mut self) {
+        let day = GAME_TIME().day;
+        let coins = &self.coins;
+        let margin = self.margin.get_or_insert_with(MarginAccount::new);
+
+        let price_of = |id: CoinId| {
+            coins
+                .iter()
+                .find(|c| c.id() == id && c.active)
+                .map(|c| c.current_price)
+        };
+
+        let interest = margin.charge_interest(day, price_of);
+        let liquidated = margin.liquidate(price_of);
+
+        if interest > 0.0 {
+            let ledger = self.ledger.get_or_insert_with(Ledger::new);
+            ledger.record_expense(day, LedgerKind::Interest, "Margin interest", interest);
+        }
+
+        self.settle_closed_margin(liquidated, "Liquidated");
+    }
+
+    pub fn spend(&mut self, cost: f64, kind: LedgerKind, note: &str) -> bool {
+        if self.bank.withdraw(cost) {
+            let day = GAME_TIME().day;
+            self.ledger_mut().record_expense(day, kind, note, cost);
+            return true;
+        }
+        false
+    }
+
+    pub fn earn(&mut self, amount: f64, kind: LedgerKind, note: &str) {
+        let day = GAME_TIME().day;
+        self.bank.deposit(amount);
+        self.ledger_mut().record_income(day, kind, note, amount);
+    }
+
+    pub fn record_mined(
+        &mut self,
+        id: CoinId,
+        name: &str,
+        price: f64,
+        share_coins: f64,
+        bonus_coins: f64,
+    ) {
+        let day = GAME_TIME().day;
+        let ledger = self.ledger_mut();
+
+        ledger.record_mined(day, id, name, LedgerKind::Mined, share_coins, price);
+        ledger.record_mined(day, id, name, LedgerKind::BlockBonus, bonus_coins, price);
+    }
+
+    /// Lists a coin. Ids reserved earlier, like an ICO's, don't move the id
+    /// counter again.
     pub fn add_coin(&mut self, coin: CryptoCoin) {
+        self.index = self.index.max(coin.id().0 + 1);
         self.coins.push(coin);
-        self.index += 1;
     }
 
     pub fn remove_coin(&mut self, coin: &CryptoCoin) -> Option<usize> {
-        let index = self.coins.iter().position(|c| c.name == coin.name);
+        let index = self.coins.iter().position(|c| c.id() == coin.id());
 
         if let Some(index) = index {
             self.coins.remove(index);
@@ -57,21 +469,43 @@ impl Market {
         index
     }
 
-    pub fn set_profit_factor(&mut self, selections: usize) {
+    /// Profit factor at the hash each coin gets from the selection's
+    /// allocation. Coins not being mined show an even share.
+    pub fn set_profit_factor(&mut self, selection: &SelectionMultiList) {
         let rig = MINING_RIG();
         let hash_rate = rig.get_hash_rate();
 
         for coin in &mut self.coins {
-            coin.profit_factor = coin.calculate_profit_factor(hash_rate / selections as u64);
+            coin.profit_weight = Some(coin.calculate_profit_factor(hash_rate));
+        }
+
+        let allocation = selection.hash_allocation(&self.coins, hash_rate);
+        let even = hash_rate / allocation.len().max(1) as u64;
+
+        for coin in &mut self.coins {
+            let hash = allocation
+                .iter()
+                .find(|(id, _)| *id == coin.id())
+                .map(|(_, hash)| *hash)
+                .unwrap_or(even);
+
+            coin.profit_factor = coin.calculate_profit_factor(hash);
         }
     }
 
     pub fn sell_coins(&mut self, coin: &CryptoCoin, amount: Option<f64>) {
-        if let Some(coin) = self.coins.iter_mut().find(|c| c.name == coin.name) {
+        let day = GAME_TIME().day;
+
+        if let Some(coin) = self.coins.iter_mut().find(|c| c.id() == coin.id()) {
             let amount = amount.unwrap_or(coin.balance);
+            let price = coin.current_price;
 
-            self.bank.deposit(amount * coin.current_price);
+            self.bank.deposit(amount * price);
             coin.balance -= amount;
+            coin.add_volume(amount * price);
+
+            let (id, name) = (coin.id(), coin.name.clone());
+            self.ledger_mut().record_sell(day, id, &name, amount, price);
         }
     }
 
@@ -80,6 +514,9 @@ impl Market {
     }
 
     pub fn sell_all_coins(&mut self) {
+        let day = GAME_TIME().day;
+        let ledger = self.ledger.get_or_insert_with(Ledger::new);
+
         for coin in self.coins.iter_mut() {
             let bal = coin.balance;
 
@@ -91,6 +528,8 @@ impl Market {
 
             self.bank.deposit(bal * price);
             coin.balance = 0.0;
+
+            ledger.record_sell(day, coin.id(), &coin.name, bal, price);
         }
     }
 
@@ -101,15 +540,36 @@ impl Market {
     }
 
     pub fn get_coin_index(&self, coin: &CryptoCoin) -> Option<usize> {
-        self.coins.iter().position(|c| c.name == coin.name)
+        self.coins.iter().position(|c| c.id() == coin.id())
     }
 
-    pub fn get_coin_by_index(&self, index: usize) -> Option<&CryptoCoin> {
-        self.coins.iter().find(|c| c.index == index)
+    /// The coin currently drawn in the given chart series slot.
+    pub fn coin_by_slot(&self, slot: usize) -> Option<&CryptoCoin> {
+        self.coins.iter().find(|c| c.active && c.index == slot)
+    }
+
+    pub fn series_slot(&self, id: CoinId) -> Option<usize> {
+        self.coin_by_id(id).filter(|c| c.active).map(|c| c.index)
+    }
+
+    /// The lowest chart series slot no active coin is drawn in.
+    pub fn free_slot(&self) -> Option<usize> {
+        (0..MAX_MARKET_SLOTS).find(|&slot| self.coin_by_slot(slot).is_none())
     }
 
     pub fn set_coin_inactive(&mut self, coin: &CryptoCoin, day: u64) {
         if let Some(index) = self.get_coin_index(coin) {
+            let price = self.coins[index].current_price;
+            let closed = self.margin_mut().close_all_for(coin.id(), price);
+            self.settle_closed_margin(closed, "Delisted");
+
+            // Delisted coins release their stake without a penalty
+            if let Some(stake) = self.coins[index].stake.take() {
+                self.coins[index].balance += stake.amount;
+            }
+
+            self.alerts_mut().forget_coin(coin.id());
+
             self.coins[index].active = false;
             self.coins[index].current_price = 0.0;
             self.coins[index].index = 100;
@@ -126,6 +586,57 @@ impl Market {
         }
     }
 
+    pub fn coin_by_id(&self, id: CoinId) -> Option<&CryptoCoin> {
+        self.coins.iter().find(|c| c.id() == id)
+    }
+
+    pub fn mut_coin_by_id(&mut self, id: CoinId) -> Option<&mut CryptoCoin> {
+        self.coins.iter_mut().find(|c| c.id() == id)
+    }
+
+    pub fn next_coin_id(&self) -> CoinId {
+        CoinId(self.index)
+    }
+
+    /// Gives coins from saves older than coin ids an id, using their "Coin-N"
+    /// name where possible, and moves the id counter past every id in use.
+    pub fn migrate_coin_ids(&mut self) {
+        let mut next = self
+            .coins
+            .iter()
+            .chain(self.inactive_coins.iter())
+            .filter_map(|c| c.id.or_else(|| c.legacy_id()))
+            .map(|id| id.0 + 1)
+            .max()
+            .unwrap_or(0)
+            .max(self.index);
+
+        for coin in self.coins.iter_mut().chain(self.inactive_coins.iter_mut()) {
+            if coin.id.is_some() {
+                continue;
+            }
+
+            coin.id = match coin.legacy_id() {
+                Some(id) => Some(id),
+                None => {
+                    next += 1;
+                    Some(CoinId(next - 1))
+                }
+            };
+        }
+
+        self.index = next;
+
+        // Ledger records were keyed by name before coins had ids
+        let known: Vec<(CoinId, String)> = self
+            .coins
+            .iter()
+            .chain(self.inactive_coins.iter())
+            .map(|c| (c.id(), c.name.clone()))
+            .collect();
+        self.ledger_mut().migrate_coin_ids(&known);
+    }
+
     pub fn coin_by_name(&self, name: &str) -> Option<&CryptoCoin> {
         self.coins.iter().find(|c| c.name == name)
     }
@@ -159,6 +670,58 @@ impl Market {
         }
     }
 
+    /// Coins for the coins table. Inactive coins always follow the active
+    /// ones, unsorted.
+    pub fn sorted_coins(&self, with_inactive: bool, sort: CoinSort) -> Vec<CryptoCoin> {
+        let mut coins = self.index_sorted_coins(with_inactive);
+        let active = coins.iter().filter(|c| c.active).count();
+
+        let key = |c: &CryptoCoin| match sort {
+            CoinSort::Slot => -(c.index as f64),
+            CoinSort::MarketCap => c.market_cap(),
+            CoinSort::Volume => c.volume_24h(),
+            CoinSort::Price => c.current_price,
+            CoinSort::Profit => c.profit_factor,
+        };
+
+        coins[..active].sort_by(|a, b| key(b).total_cmp(&key(a)));
+        coins
+    }
+
+    pub fn total_market_cap(&self) -> f64 {
+        self.coins
+            .iter()
+            .filter(|c| c.active)
+            .map(|c| c.market_cap())
+            .sum()
+    }
+
+    /// The coin's share of the market cap of all active coins.
+    pub fn dominance(&self, coin: &CryptoCoin) -> f64 {
+        let total = self.total_market_cap();
+
+        if total <= 0.0 || !coin.active {
+            return 0.0;
+        }
+        coin.market_cap() / total
+    }
+
+    /// Position of the coin when active coins are ranked by market cap.
+    pub fn cap_rank(&self, coin: &CryptoCoin) -> Option<usize> {
+        if !coin.active {
+            return None;
+        }
+
+        let cap = coin.market_cap();
+        let above = self
+            .coins
+            .iter()
+            .filter(|c| c.active && c.market_cap() > cap)
+            .count();
+
+        Some(above + 1)
+    }
+
     pub fn get_profit_sorted_coins(&self) -> Vec<CryptoCoin> {
         let mut coins = self.coins.clone();
         coins.sort_by(|a, b| a.profit_factor.partial_cmp(&b.profit_factor).unwrap());
@@ -171,30 +734,124 @@ impl Market {
     }
 
     pub fn simulate_day(&mut self) {
+        let market_return = self.sentiment_mut().step();
+
+        if let Some(shock) = self.sentiment().last_shock {
+            let msg = if shock < 0.0 {
+                format!(
+                    "Market-wide crash! Coins drop about {:.0}%.",
+                    shock * -100.0
+                )
+            } else {
+                format!("Market-wide rally! Coins jump about {:.0}%.", shock * 100.0)
+            };
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+
+        let listed = self
+            .coins
+            .iter()
+            .filter(|c| c.active)
+            .map(|c| (c.id(), c.name.clone()))
+            .collect::<Vec<(CoinId, String)>>();
+        let day = GAME_TIME().day;
+
+        for event in self.news_mut().roll(&listed, day) {
+            let msg = format!("NEWS: {}", event.headline);
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+
+        let coins = &self.coins;
+        let npc_moves = self.npcs.get_or_insert_with(NpcDesk::new).step(coins);
+
+        for npc_move in npc_moves {
+            let headline = npc_move.headline();
+            self.news_mut().report(
+                &headline,
+                (npc_move.coin, &npc_move.coin_name),
+                npc_move.impact,
+                day,
+            );
+
+            let msg = format!("MARKET: {headline}");
+            spawn_local(async move {
+                command_line_output(&msg).await;
+            });
+        }
+
+        let news = self.news();
+        let npcs = self.npcs();
+
+        let rig_hash = MINING_RIG().get_hash_rate();
+
         for coin in &mut self.coins {
-            coin.update_price();
+            let news_impact = news.impact_for(coin.id());
+            let npc_flow = npcs.flow_for(coin.id());
+            let npc_impact = price_impact(coin, npc_flow);
+            let starting_price = coin.current_price;
+            coin.update_price(market_return, news_impact, npc_impact);
+
+            coin.ensure_chain(rig_hash);
+            coin.set_rival_hash(rig_hash as f64 * npcs.rival_hash_multiplier(coin.id()));
+            coin.step_chain();
+
+            if coin.active {
+                coin.mine_rival_blocks();
+            }
+
+            if coin.active {
+                let change = coin.current_price / starting_price.max(0.0001) - 1.0;
+                coin.ensure_supply();
+                coin.step_supply(change);
+                coin.add_volume(npc_flow.abs());
+                coin.record_profit_factor();
+            }
         }
+
+        self.news_mut().advance();
+
+        self.run_margin();
+        self.run_staking();
+        self.run_alerts();
     }
 
     pub fn simulate_day_single(&mut self, coin: &CryptoCoin) {
-        if let Some(coin) = self.coins.iter_mut().find(|c| c.name == coin.name) {
-            coin.update_price();
+        let market_return = self.sentiment().last_return;
+        let news_impact = self.news().impact_for(coin.id());
+
+        if let Some(coin) = self.coins.iter_mut().find(|c| c.id() == coin.id()) {
+            coin.update_price(market_return, news_impact, 0.0);
         }
     }
 
     pub fn run_rug_pull(&mut self, day: u64) {
+        let ledger = self.ledger.get_or_insert_with(Ledger::new);
+        let mut rugged = Vec::new();
+
         for coin in &mut self.coins {
-            let rug_chance = coin.calculate_rug_chance();
-            if rand_from_range(0.0..1.0) < rug_chance {
-                // Rug pull chance
+            coin.roll_rug_risk();
+
+            if coin.tick_rug_risk() {
+                coin.rug_risk = None;
 
                 let rug_protection_active = MINING_RIG().get_rug_protection_active();
 
-                if rug_protection_active && coin.balance > 0.0 {
+                let mut protected_amount = 0.0;
+                let mut protection_value = 0.0;
+
+                // Staked coins are exposed to the rug the same as the balance
+                let exposed = coin.balance + coin.staked_amount();
+                coin.stake = None;
+
+                if rug_protection_active && exposed > 0.0 {
                     let rug_protection_amount = MINING_RIG().get_rug_protection_amount();
 
-                    let protected_amount = coin.balance * rug_protection_amount;
-                    let protection_value = protected_amount * coin.current_price;
+                    protected_amount = exposed * rug_protection_amount;
+                    protection_value = protected_amount * coin.current_price;
 
                     self.bank.deposit(protection_value);
 
@@ -207,6 +864,15 @@ impl Market {
                     });
                 }
 
+                ledger.record_rug_pull(
+                    day,
+                    coin.id(),
+                    &coin.name,
+                    protected_amount,
+                    protection_value,
+                    coin.current_price,
+                );
+
                 let msg = format!("{} has been rug pulled!", coin.name);
 
                 spawn_local(async move {
@@ -215,49 +881,88 @@ impl Market {
 
                 coin.current_price = 0.0;
                 coin.death_date = Some(day);
+                rugged.push(coin.id());
             }
         }
-    }
 
-    pub fn mut_get_any_share_cooldown(&mut self) -> Option<&mut CryptoCoin> {
-        let longest_cooldown = self
-            .coins
-            .iter_mut()
-            .filter(|c| c.get_share_cooldown() > 0)
-            .max_by(|a, b| a.get_share_cooldown().cmp(&b.get_share_cooldown()));
-        longest_cooldown
-    }
-
-    pub fn get_any_share_cooldown(&self) -> Option<&CryptoCoin> {
-        let longest_cooldown = self
-            .coins
-            .iter()
-            .filter(|c| c.get_share_cooldown() > 0)
-            .max_by(|a, b| a.get_share_cooldown().cmp(&b.get_share_cooldown()));
-        longest_cooldown
-    }
-
-    pub fn decrement_all_share_cooldowns(&mut self) {
-        for coin in &mut self.coins {
-            coin.decrement_share_cooldown();
+        // Shorts on a rugged coin are covered for nothing
+        for id in rugged {
+            let closed = self.margin_mut().close_all_for(id, 0.0);
+            self.settle_closed_margin(closed, "Rug pull");
         }
     }
 
     pub fn buy_coin(&mut self, coin: &CryptoCoin, amount: f64) -> bool {
-        if let Some(coin) = self.coins.iter_mut().find(|c| c.name == coin.name) {
-            let cost = coin.current_price * amount;
+        let day = GAME_TIME().day;
+
+        if let Some(coin) = self.coins.iter_mut().find(|c| c.id() == coin.id()) {
+            let price = coin.current_price;
+            let cost = price * amount;
 
             if self.bank.withdraw(cost) {
                 coin.balance += amount;
+                coin.add_volume(cost);
+
+                let (id, name) = (coin.id(), coin.name.clone());
+                self.ledger_mut().record_buy(day, id, &name, amount, price);
                 return true;
             }
         }
         false
     }
 
+    pub fn swap_quote(&self, from: CoinId, to: CoinId, amount: f64) -> Option<SwapQuote> {
+        let from = self.coin_by_id(from).filter(|c| c.active)?;
+        let to = self.coin_by_id(to).filter(|c| c.active)?;
+
+        SwapQuote::new(from, to, amount)
+    }
+
+    /// Swaps one coin's balance straight into another without going through
+    /// the bank. Nothing changes unless the whole swap can go through.
+    pub fn swap(&mut self, from: CoinId, to: CoinId, amount: f64) -> Option<SwapQuote> {
+        let quote = self.swap_quote(from, to, amount)?;
+        let day = GAME_TIME().day;
+
+        let from_coin = self.mut_coin_by_id(from)?;
+        if from_coin.balance < amount {
+            return None;
+        }
+        let from_price = from_coin.current_price;
+        from_coin.balance -= amount;
+        from_coin.add_volume(amount * from_price);
+
+        let to_coin = self.mut_coin_by_id(to)?;
+        let to_price = to_coin.current_price;
+        to_coin.balance += quote.amount_out;
+        to_coin.add_volume(quote.amount_out * to_price);
+
+        self.ledger_mut().record_swap(
+            day,
+            (from, &quote.from_name, amount, from_price),
+            (to, &quote.to_name, quote.amount_out, to_price),
+        );
+
+        Some(quote)
+    }
+
+    /// Routes every other held coin into the target coin.
+    pub fn swap_all_into(&mut self, to: CoinId) -> Vec<SwapQuote> {
+        let held: Vec<(CoinId, f64)> = self
+            .coins
+            .iter()
+            .filter(|c| c.active && c.id() != to && c.balance > 0.0)
+            .map(|c| (c.id(), c.balance))
+            .collect();
+
+        held.into_iter()
+            .filter_map(|(from, amount)| self.swap(from, to, amount))
+            .collect()
+    }
+
     pub fn get_max_buyable(&self, coin: &CryptoCoin) -> f64 {
         let bal = self.bank.balance;
-        let coin = self.coins.iter().find(|c| c.name == coin.name).unwrap();
+        let coin = self.coins.iter().find(|c| c.id() == coin.id()).unwrap();
         let price = coin.current_price;
 
         bal / price
@@ -268,23 +973,94 @@ impl Market {
         self.buy_coin(coin, max_buyable)
     }
 
-    pub fn get_newest_coin(&self) -> Option<CryptoCoin> {
-        let newest_idx = self.index - 1;
+    /// The cheapest active coin the player has nothing in, the one to give
+    /// up when a new coin needs a slot.
+    pub fn cheapest_unheld(&self, exclude: Option<CoinId>) -> Option<CoinId> {
+        let margin = self.margin();
+
         self.coins
             .iter()
-            .find(|c| {
-                let name = c.name.clone();
-                let name_split = name.split('-').collect::<Vec<&str>>();
-                let curr_idx = name_split[1].parse::<u64>().unwrap();
-                curr_idx == newest_idx
-            })
-            .cloned()
+            .filter(|c| c.active && c.balance <= 0.0 && c.stake.is_none())
+            .filter(|c| Some(c.id()) != exclude)
+            .filter(|c| margin.positions_for(c.id()).is_empty())
+            .min_by(|a, b| a.current_price.total_cmp(&b.current_price))
+            .map(|c| c.id())
+    }
+
+    /// Rolls for a hard fork among established coins, at most one per update.
+    pub fn roll_fork(&self) -> Option<CoinId> {
+        self.coins
+            .iter()
+            .filter(|c| can_fork(c, &self.coins))
+            .find(|_| rand_from_range(0.0..1.0) < FORK_CHANCE)
+            .map(|c| c.id())
+    }
+
+    /// Splits the parent into a fork in the given chart slot and airdrops
+    /// holders a matching balance of the new coin.
+    pub fn fork_coin(&mut self, parent: CoinId, index: usize, day: u64) -> Option<CryptoCoin> {
+        let parent = self.coin_by_id(parent)?.clone();
+        let rig_hash = MINING_RIG().get_hash_rate();
+        let mut coin = split(
+            &parent,
+            self.next_coin_id(),
+            index,
+            day,
+            &self.taken_names(),
+            rig_hash,
+        );
+
+        // Staked coins count, they were on the chain when it split
+        let airdrop = parent.balance + parent.staked_amount();
+        coin.balance = airdrop;
+
+        let price = coin.current_price;
+        self.ledger_mut().record_mined(
+            day,
+            coin.id(),
+            &coin.name,
+            LedgerKind::Fork,
+            airdrop,
+            price,
+        );
+
+        let headline = format!("{} hard forks into {}", parent.name, coin.name);
+        self.news_mut()
+            .report(&headline, (parent.id(), &parent.name), 0.0, day);
+
+        let msg = if airdrop > 0.0 {
+            format!(
+                "FORK: {headline}! You received {} {}.",
+                truncate_price(airdrop),
+                coin.name
+            )
+        } else {
+            format!("FORK: {headline}!")
+        };
+        spawn_local(async move {
+            command_line_output(&msg).await;
+        });
+
+        self.add_coin(coin.clone());
+        Some(coin)
+    }
+
+    pub fn taken_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .coins
+            .iter()
+            .chain(self.inactive_coins.iter())
+            .map(|c| c.name.clone())
+            .collect();
+
+        names.extend(self.ico().names());
+        names
     }
 
     pub fn get_coin_prince(&self, coin: &CryptoCoin) -> f64 {
         self.coins
             .iter()
-            .find(|c| c.name == coin.name)
+            .find(|c| c.id() == coin.id())
             .map(|c| c.current_price)
             .unwrap_or(0.0)
     }
@@ -317,7 +1093,7 @@ impl Market {
         labels
     }
 
-    fn get_labels(&self) -> Vec<String> {
+    fn get_labels(&self, now: &GameTime) -> Vec<String> {
         let mut max_len = 0;
 
         for coin in &self.coins {
@@ -326,17 +1102,15 @@ impl Market {
             }
         }
 
-        let mut labels = Vec::new();
-
-        for _ in 0..max_len {
-            labels.push("|".to_string());
-        }
-
-        labels
+        // Rebuild the labels back from now, one market update per 15 minutes
+        (0..max_len)
+            .rev()
+            .map(|i| now.minutes_ago(i as u64 * 15).chart_label())
+            .collect()
     }
 
-    pub fn get_chart(&self) -> MarketChart {
-        let labels = self.get_labels();
+    pub fn get_chart(&self, now: &GameTime) -> MarketChart {
+        let labels = self.get_labels(now);
         let series = self.get_sersies();
         let series_labels = self.get_series_labels();
 
@@ -367,22 +1141,92 @@ pub fn cull_market(
         let mined_out = coin.blocks >= coin.max_blocks;
         let has_bal = coin.balance > 0.0;
         if coin.current_price < 0.01 || (mined_out && !has_bal) {
-            replace_coin(&coin, series_labels, series, rig_lvl, day);
+            replace_coin(coin.id(), series_labels, series, rig_lvl, day);
         }
     }
+
+    // An ICO that has waited too long for a free slot takes the cheapest
+    // coin the player has nothing in
+    if MARKET().ico().has_overdue(day) {
+        let cheapest = MARKET().cheapest_unheld(None);
+
+        if let Some(id) = cheapest {
+            replace_coin(id, series_labels, series, rig_lvl, day);
+        }
+    }
+
+    while MARKET().ico().needs_offering() {
+        let coin = gen_random_coin(0, rig_lvl);
+        MARKET.write().announce_ico(coin, day);
+    }
+}
+
+/// Rolls for a hard fork and gives the new coin a chart slot, a new one while
+/// the market has room or the cheapest unheld coin's otherwise.
+pub fn run_forks(
+    series_labels: &mut Signal<Vec<String>>,
+    series: &mut Signal<Vec<Vec<f64>>>,
+    day: u64,
+) {
+    let parent = match MARKET().roll_fork() {
+        Some(id) => id,
+        None => return,
+    };
+
+    let mut mkt = MARKET();
+    let index = if let Some(slot) = mkt.free_slot() {
+        slot
+    } else {
+        let coin = match mkt
+            .cheapest_unheld(Some(parent))
+            .and_then(|id| mkt.coin_by_id(id))
+        {
+            Some(coin) => coin.clone(),
+            None => return,
+        };
+
+        SELECTION.write().unmake_selection(coin.id());
+        SELECTION().update_ui();
+
+        mkt.set_coin_inactive(&coin, day);
+        coin.index
+    };
+
+    let coin = match mkt.fork_coin(parent, index, day) {
+        Some(coin) => coin,
+        None => return,
+    };
+
+    let mut current_series = series.write();
+    let mut series_labels = series_labels.write();
+
+    if index < current_series.len() {
+        current_series[index] = coin.prices.clone();
+        series_labels[index] = coin.name.clone();
+    } else {
+        current_series.push(coin.prices.clone());
+        series_labels.push(coin.name.clone());
+    }
+
+    *MARKET.write() = mkt;
 }
 
+/// Swaps the coin for a listed ICO or a fresh coin in the same chart slot.
+/// Returns the id of the coin put in its place. A listed ICO keeps the id it
+/// was announced with, so this is not always the newest id.
 pub fn replace_coin(
-    coin: &CryptoCoin,
+    id: CoinId,
     series_labels: &mut Signal<Vec<String>>,
     series: &mut Signal<Vec<Vec<f64>>>,
     rig_lvl: u64,
     day: u64,
-) {
+) -> Option<CoinId> {
     let mut mkt = MARKET();
-    let series_index = coin.index;
 
-    SELECTION.write().unmake_selection(series_index);
+    let coin = mkt.coin_by_id(id)?.clone();
+    let series_index = mkt.series_slot(id)?;
+
+    SELECTION.write().unmake_selection(id);
     SELECTION().update_ui();
 
     mkt.set_coin_inactive(&coin, day);
@@ -390,7 +1234,10 @@ pub fn replace_coin(
     let mut current_series = series.write();
     current_series[series_index].clear();
 
-    let new_coin = gen_random_coin(series_index, rig_lvl);
+    let new_coin = match mkt.list_ico(series_index, day) {
+        Some(coin) => coin,
+        None => gen_random_coin(series_index, rig_lvl),
+    };
     mkt.add_coin(new_coin.clone());
 
     let mut series_labels = series_labels.write();
@@ -398,16 +1245,16 @@ pub fn replace_coin(
     series_labels[series_index] = new_coin.name.clone();
 
     *MARKET.write() = mkt;
+
+    Some(new_coin.id())
 }
 
 pub fn gen_random_coin(index: usize, rig_lvl: u64) -> CryptoCoin {
-    let volitility = rand_from_range(0.02..0.08);
     let mkt = MARKET();
 
-    let coin_name = { format!("Coin-{}", mkt.index) };
+    let archetype = CoinArchetype::random();
+    let identity = CoinIdentity::generate(archetype, &mkt.taken_names());
 
-    let shares_per_block = 1000;
-    let block_reward = 100.0;
     let max_blocks = match rig_lvl {
         0..=25 => rand_from_range(10.0..25.0) as u64,
         26..=50 => rand_from_range(15.0..50.0) as u64,
@@ -482,26 +1329,31 @@ pub fn gen_random_coin(index: usize, rig_lvl: u64) -> CryptoCoin {
         _ => 200.0..220.0,
     };
 
-    CryptoCoin::new(
-        &coin_name,
+    let mut coin = CryptoCoin::with_archetype(
+        archetype,
+        &identity.name,
         rand_from_range(price_range),
-        -volitility..volitility,
         index,
-        shares_per_block,
-        block_reward,
         max_blocks,
         hashes_per_share,
         berth_date,
-    )
-}
+    );
+
+    coin.set_identity(mkt.next_coin_id(), identity);
+    coin.ensure_chain(MINING_RIG().get_hash_rate());
+    coin.ensure_supply();
 
-pub fn gen_random_coin_with_set_index(index: usize, rig_lvl: u64) -> CryptoCoin {
-    let volitility = rand_from_range(0.02..0.08);
+    coin
+}
 
-    let coin_name = { format!("Coin-{}", index) };
+pub fn gen_random_coin_with_set_index(
+    index: usize,
+    rig_lvl: u64,
+    taken_names: &[String],
+) -> CryptoCoin {
+    let archetype = CoinArchetype::random();
+    let identity = CoinIdentity::generate(archetype, taken_names);
 
-    let shares_per_block = 1000;
-    let block_reward = 100.0;
     let max_blocks = rand_from_range(10.0..25.0) as u64;
 
     let max_hashes_per_share = (rig_lvl * 1000).min(5_000);
@@ -510,15 +1362,19 @@ pub fn gen_random_coin_with_set_index(index: usize, rig_lvl: u64) -> CryptoCoin
 
     let berth_date = GAME_TIME().day;
 
-    CryptoCoin::new(
-        &coin_name,
+    let mut coin = CryptoCoin::with_archetype(
+        archetype,
+        &identity.name,
         rand_from_range(8.0..20.0),
-        -volitility..volitility,
         index,
-        shares_per_block,
-        block_reward,
         max_blocks,
         hashes_per_share,
         berth_date,
-    )
+    );
+
+    coin.set_identity(CoinId(index as u64), identity);
+    coin.ensure_chain(MINING_RIG().get_hash_rate());
+    coin.ensure_supply();
+
+    coin
 }
@@ -2,6 +2,9 @@
 use dioxus::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::auto_mining::{AutoMiner, MAX_AUTO_MINING_LEVEL};
+use crate::latency::{network_latency_ms, MAX_NETWORK_LEVEL};
+use crate::rug_risk::MAX_DUE_DILIGENCE_LEVEL;
 use crate::utils::get_season;
 
 pub static MINING_RIG: GlobalSignal<MiningRig> = Signal::global(|| MiningRig::new());
@@ -111,6 +114,9 @@ pub struct MiningRig {
     pub auto_power_fill: Option<AutoPowerFill>,
     pub rug_protection: RugProtection,
     pub auto_mining_level: Option<u64>,
+    pub due_diligence: Option<u64>,
+    pub auto_miner: Option<AutoMiner>,
+    pub network_level: Option<u64>,
 }
 
 impl MiningRig {
@@ -132,6 +138,9 @@ impl MiningRig {
             auto_power_fill: None,
             rug_protection: RugProtection::new(),
             auto_mining_level: None,
+            due_diligence: None,
+            auto_miner: None,
+            network_level: None,
         }
     }
 
@@ -207,8 +216,93 @@ impl MiningRig {
         }
     }
 
-    pub fn get_global_share_cooldown(&self) -> bool {
-        !self.rug_protection.active
+    pub fn get_due_diligence_level(&self) -> u64 {
+        self.due_diligence.unwrap_or(0)
+    }
+
+    pub fn get_due_diligence_upgrade_cost(&self) -> f64 {
+        match self.get_due_diligence_level() {
+            0 => 2_500.0,
+            1 => 25_000.0,
+            _ => 250_000.0,
+        }
+    }
+
+    pub fn upgrade_due_diligence(&mut self) {
+        if self.get_due_diligence_level() >= MAX_DUE_DILIGENCE_LEVEL {
+            return;
+        }
+        self.due_diligence = Some(self.get_due_diligence_level() + 1);
+    }
+
+    pub fn get_auto_mining_level(&self) -> u64 {
+        self.auto_mining_level.unwrap_or(0)
+    }
+
+    pub fn get_auto_mining_upgrade_cost(&self) -> f64 {
+        match self.get_auto_mining_level() {
+            0 => 50_000.0,
+            1 => 250_000.0,
+            2 => 1_000_000.0,
+            3 => 5_000_000.0,
+            _ => 25_000_000.0,
+        }
+    }
+
+    pub fn upgrade_auto_mining(&mut self) {
+        if self.get_auto_mining_level() >= MAX_AUTO_MINING_LEVEL {
+            return;
+        }
+        self.auto_mining_level = Some(self.get_auto_mining_level() + 1);
+        self.auto_miner.get_or_insert_with(AutoMiner::new);
+    }
+
+    pub fn auto_miner(&self) -> AutoMiner {
+        self.auto_miner.clone().unwrap_or_default()
+    }
+
+    pub fn auto_miner_mut(&mut self) -> &mut AutoMiner {
+        self.auto_miner.get_or_insert_with(AutoMiner::new)
+    }
+
+    pub fn get_auto_mining_active(&self) -> bool {
+        self.get_auto_mining_level() > 0 && self.auto_miner().active
+    }
+
+    pub fn toggle_auto_mining(&mut self) {
+        let miner = self.auto_miner_mut();
+        miner.active = !miner.active;
+        miner.updates_since_rank = 0;
+    }
+
+    pub fn get_network_level(&self) -> u64 {
+        self.network_level.unwrap_or(0)
+    }
+
+    pub fn get_network_latency_ms(&self) -> f64 {
+        network_latency_ms(self.get_network_level())
+    }
+
+    pub fn get_network_upgrade_cost(&self) -> f64 {
+        match self.get_network_level() {
+            0 => 1_000.0,
+            1 => 15_000.0,
+            2 => 150_000.0,
+            3 => 1_500_000.0,
+            _ => 15_000_000.0,
+        }
+    }
+
+    pub fn upgrade_network(&mut self) {
+        if self.get_network_level() >= MAX_NETWORK_LEVEL {
+            return;
+        }
+        self.network_level = Some(self.get_network_level() + 1);
+    }
+
+    /// Multi-mining comes with rug protection, as it always has.
+    pub fn get_multi_mining_unlocked(&self) -> bool {
+        self.rug_protection.active
     }
 
     pub fn upgrade_rug_protection(&mut self) {
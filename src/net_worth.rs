@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+/// Oldest snapshots are dropped past this many days.
+pub const MAX_SNAPSHOTS: usize = 365;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NetWorthSnapshot {
+    pub day: u64,
+    pub bank: f64,
+    pub coins: f64,
+    pub staked: f64,
+    pub margin: f64,
+    pub presale: f64,
+    pub nfts: f64,
+}
+
+impl NetWorthSnapshot {
+    pub fn total(&self) -> f64 {
+        self.bank + self.coins + self.staked + self.margin + self.presale + self.nfts
+    }
+
+    /// (label, value) pairs for each part of net worth.
+    pub fn parts(&self) -> Vec<(&'static str, f64)> {
+        vec![
+            ("Bank", self.bank),
+            ("Coins", self.coins),
+            ("Staked", self.staked),
+            ("Margin", self.margin),
+            ("Presales", self.presale),
+            ("NFT Studio", self.nfts),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NetWorthHistory {
+    pub snapshots: Vec<NetWorthSnapshot>,
+}
+
+impl NetWorthHistory {
+    pub fn new() -> Self {
+        NetWorthHistory {
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Records the snapshot, replacing the one for the same day so each day
+    /// keeps its latest value.
+    pub fn record(&mut self, snapshot: NetWorthSnapshot) {
+        match self.snapshots.last_mut() {
+            Some(last) if last.day == snapshot.day => *last = snapshot,
+            _ => self.snapshots.push(snapshot),
+        }
+
+        while self.snapshots.len() > MAX_SNAPSHOTS {
+            self.snapshots.remove(0);
+        }
+    }
+
+    pub fn latest(&self) -> Option<&NetWorthSnapshot> {
+        self.snapshots.last()
+    }
+
+    /// Change in net worth since the previous day's snapshot.
+    pub fn daily_change(&self) -> f64 {
+        let len = self.snapshots.len();
+
+        if len < 2 {
+            return 0.0;
+        }
+        self.snapshots[len - 1].total() - self.snapshots[len - 2].total()
+    }
+}
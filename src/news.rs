@@ -0,0 +1,261 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::crypto_coin::CoinId;
+use crate::utils::rand_from_range;
+
+pub const MAX_HEADLINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NewsScope {
+    Coin,
+    Market,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewsTemplate {
+    pub scope: NewsScope,
+    pub headline: &'static str,
+    pub impact: Range<f64>,
+    pub duration: u64,
+    pub weight: f64,
+}
+
+// `{coin}` in a headline is replaced with the affected coin's name. Impact is
+// the total price move spread over the duration, in market updates.
+pub const NEWS_TEMPLATES: [NewsTemplate; 12] = [
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "{coin} listed on a major exchange",
+        impact: 0.08..0.2,
+        duration: 8,
+        weight: 1.0,
+    },
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "Celebrity tweets support for {coin}",
+        impact: 0.05..0.15,
+        duration: 3,
+        weight: 1.5,
+    },
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "{coin} announces partnership with payment provider",
+        impact: 0.04..0.1,
+        duration: 12,
+        weight: 1.0,
+    },
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "{coin} completes successful protocol upgrade",
+        impact: 0.02..0.06,
+        duration: 16,
+        weight: 1.0,
+    },
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "Protocol exploit drains {coin} treasury",
+        impact: -0.25..-0.1,
+        duration: 4,
+        weight: 0.7,
+    },
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "{coin} delisted from exchange",
+        impact: -0.18..-0.08,
+        duration: 8,
+        weight: 0.8,
+    },
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "{coin} developers quietly leave the project",
+        impact: -0.1..-0.04,
+        duration: 12,
+        weight: 1.0,
+    },
+    NewsTemplate {
+        scope: NewsScope::Coin,
+        headline: "Whale dumps large {coin} holdings",
+        impact: -0.08..-0.03,
+        duration: 2,
+        weight: 1.5,
+    },
+    NewsTemplate {
+        scope: NewsScope::Market,
+        headline: "Regulators announce crypto crackdown",
+        impact: -0.08..-0.03,
+        duration: 16,
+        weight: 1.0,
+    },
+    NewsTemplate {
+        scope: NewsScope::Market,
+        headline: "Major bank launches crypto custody",
+        impact: 0.03..0.07,
+        duration: 16,
+        weight: 1.0,
+    },
+    NewsTemplate {
+        scope: NewsScope::Market,
+        headline: "Exchange outage freezes withdrawals",
+        impact: -0.05..-0.02,
+        duration: 4,
+        weight: 1.0,
+    },
+    NewsTemplate {
+        scope: NewsScope::Market,
+        headline: "Interest rate cut boosts risk assets",
+        impact: 0.02..0.05,
+        duration: 24,
+        weight: 1.0,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewsEvent {
+    pub headline: String,
+    pub coin: Option<String>,
+    pub coin_id: Option<CoinId>,
+    pub impact: f64,
+    pub duration: u64,
+    pub remaining: u64,
+    pub day: u64,
+}
+
+impl NewsEvent {
+    pub fn impact_per_update(&self) -> f64 {
+        if self.duration == 0 {
+            return 0.0;
+        }
+        self.impact / self.duration as f64
+    }
+
+    /// Market-wide events cover every coin. Coin events from saves older than
+    /// coin ids cover none, they run out within a day.
+    pub fn applies_to(&self, coin: CoinId) -> bool {
+        match (self.coin_id, &self.coin) {
+            (Some(id), _) => id == coin,
+            (None, Some(_)) => false,
+            (None, None) => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NewsFeed {
+    pub active: Vec<NewsEvent>,
+    pub headlines: Vec<NewsEvent>,
+}
+
+impl NewsFeed {
+    pub fn new() -> Self {
+        NewsFeed {
+            active: Vec::new(),
+            headlines: Vec::new(),
+        }
+    }
+
+    fn pick_template(scope: NewsScope) -> &'static NewsTemplate {
+        let mut templates = NEWS_TEMPLATES.iter().filter(|t| t.scope == scope);
+        let total_weight: f64 = templates.clone().map(|t| t.weight).sum();
+        let mut roll = rand_from_range(0.0..total_weight);
+
+        for template in templates.clone() {
+            if roll < template.weight {
+                return template;
+            }
+            roll -= template.weight;
+        }
+
+        templates
+            .next_back()
+            .expect("every scope should have a template")
+    }
+
+    /// Rolls for new headlines once per market update and returns any that
+    /// broke so they can be announced.
+    pub fn roll(&mut self, coins: &[(CoinId, String)], day: u64) -> Vec<NewsEvent> {
+        let mut broke = Vec::new();
+
+        if !coins.is_empty() && rand_from_range(0.0..1.0) < 0.04 {
+            let (id, coin) = &coins[rand_from_range(0.0..coins.len() as f64) as usize];
+            let template = NewsFeed::pick_template(NewsScope::Coin);
+
+            broke.push(NewsEvent {
+                headline: template.headline.replace("{coin}", coin),
+                coin: Some(coin.clone()),
+                coin_id: Some(*id),
+                impact: rand_from_range(template.impact.clone()),
+                duration: template.duration,
+                remaining: template.duration,
+                day,
+            });
+        }
+
+        if rand_from_range(0.0..1.0) < 0.01 {
+            let template = NewsFeed::pick_template(NewsScope::Market);
+
+            broke.push(NewsEvent {
+                headline: template.headline.to_string(),
+                coin: None,
+                coin_id: None,
+                impact: rand_from_range(template.impact.clone()),
+                duration: template.duration,
+                remaining: template.duration,
+                day,
+            });
+        }
+
+        for event in &broke {
+            self.active.push(event.clone());
+            self.headlines.push(event.clone());
+        }
+
+        while self.headlines.len() > MAX_HEADLINES {
+            self.headlines.remove(0);
+        }
+
+        broke
+    }
+
+    /// The combined price move this update from every active event that
+    /// covers the coin.
+    pub fn impact_for(&self, coin: CoinId) -> f64 {
+        self.active
+            .iter()
+            .filter(|e| e.applies_to(coin))
+            .map(|e| e.impact_per_update())
+            .sum()
+    }
+
+    pub fn advance(&mut self) {
+        for event in &mut self.active {
+            event.remaining = event.remaining.saturating_sub(1);
+        }
+        self.active.retain(|e| e.remaining > 0);
+    }
+
+    /// Adds a headline for something that already happened, it has no
+    /// lasting effect on prices.
+    pub fn report(&mut self, headline: &str, coin: (CoinId, &str), impact: f64, day: u64) {
+        let (id, name) = coin;
+
+        self.headlines.push(NewsEvent {
+            headline: headline.to_string(),
+            coin: Some(name.to_string()),
+            coin_id: Some(id),
+            impact,
+            duration: 0,
+            remaining: 0,
+            day,
+        });
+
+        while self.headlines.len() > MAX_HEADLINES {
+            self.headlines.remove(0);
+        }
+    }
+
+    pub fn latest(&self) -> Vec<NewsEvent> {
+        self.headlines.iter().rev().cloned().collect()
+    }
+}
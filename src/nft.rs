@@ -120,6 +120,12 @@ impl NftStudio {
         self.money_per_second() * self.popularity()
     }
 
+    /// Studio value for net worth, a game day of income at the current
+    /// popularity (96 market updates of 60 ticks at 20 ticks a second).
+    pub fn value(&self) -> f64 {
+        self.money_per_second_adjusted() * 288.0
+    }
+
     pub fn money_per_tick(&self) -> f64 {
         (self.money_per_second() / 20.0) * self.popularity()
     }
@@ -0,0 +1,399 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::crypto_coin::{CoinId, CryptoCoin};
+use crate::rug_risk::MAX_DUE_DILIGENCE_LEVEL;
+use crate::utils::rand_from_range;
+
+/// Coins with a smaller market cap than this are treated as this big when
+/// working out how far a trade moves the price.
+pub const MIN_IMPACT_CAP: f64 = 50_000.0;
+/// Price move from NPC trading in one update is capped either way.
+pub const MAX_NPC_IMPACT: f64 = 0.15;
+/// Moves that shift the price more than this get reported.
+pub const NOTABLE_IMPACT: f64 = 0.02;
+/// Updates the momentum traders look back over.
+pub const MOMENTUM_WINDOW: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NpcStrategy {
+    /// Builds big positions in small coins and dumps them into the pump.
+    Whale,
+    /// Buys whatever has been rising and sells once it turns.
+    Momentum,
+    /// Holds a few coins and sells everything at the first sign of trouble.
+    PanicSeller,
+    /// Points its hash at the most profitable coin.
+    RivalMiner,
+}
+
+impl NpcStrategy {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NpcStrategy::Whale => "Whale",
+            NpcStrategy::Momentum => "Momentum Trader",
+            NpcStrategy::PanicSeller => "Panic Seller",
+            NpcStrategy::RivalMiner => "Rival Miner",
+        }
+    }
+
+    fn capital(&self) -> Range<f64> {
+        match self {
+            NpcStrategy::Whale => 500_000.0..2_000_000.0,
+            NpcStrategy::Momentum => 50_000.0..200_000.0,
+            NpcStrategy::PanicSeller => 20_000.0..100_000.0,
+            NpcStrategy::RivalMiner => 0.0..0.0,
+        }
+    }
+}
+
+// Name and strategy of every agent on the desk.
+const NPC_ROSTER: [(&str, NpcStrategy); 9] = [
+    ("Moby Dick Capital", NpcStrategy::Whale),
+    ("0xDeepPockets", NpcStrategy::Whale),
+    ("ChartWizard", NpcStrategy::Momentum),
+    ("TrendRider", NpcStrategy::Momentum),
+    ("Line Go Up Fund", NpcStrategy::Momentum),
+    ("Paper Hands Pete", NpcStrategy::PanicSeller),
+    ("NervousNancy", NpcStrategy::PanicSeller),
+    ("HashHog Pool", NpcStrategy::RivalMiner),
+    ("Basement Farms", NpcStrategy::RivalMiner),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NpcHolding {
+    pub coin: CoinId,
+    pub amount: f64,
+    pub entry_price: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Npc {
+    pub name: String,
+    pub strategy: NpcStrategy,
+    pub cash: f64,
+    pub holdings: Vec<NpcHolding>,
+    /// Rival miners bring this multiple of the player's hash rate.
+    pub hash_multiplier: f64,
+    pub target: Option<CoinId>,
+}
+
+impl Npc {
+    pub fn new(name: &str, strategy: NpcStrategy) -> Self {
+        let hash_multiplier = match strategy {
+            NpcStrategy::RivalMiner => rand_from_range(0.5..3.0),
+            _ => 0.0,
+        };
+
+        Npc {
+            name: name.to_string(),
+            strategy,
+            cash: rand_from_range(strategy.capital()),
+            holdings: Vec::new(),
+            hash_multiplier,
+            target: None,
+        }
+    }
+
+    fn holding(&self, coin: CoinId) -> Option<&NpcHolding> {
+        self.holdings.iter().find(|h| h.coin == coin)
+    }
+
+    /// Spends `value` of cash on the coin and returns the dollars traded.
+    fn buy(&mut self, coin: &CryptoCoin, value: f64) -> f64 {
+        let value = value.min(self.cash);
+
+        if value <= 0.0 || coin.current_price <= 0.0 {
+            return 0.0;
+        }
+
+        let amount = value / coin.current_price;
+        self.cash -= value;
+
+        match self.holdings.iter_mut().find(|h| h.coin == coin.id()) {
+            Some(holding) => {
+                let cost = holding.amount * holding.entry_price + value;
+                holding.amount += amount;
+                holding.entry_price = cost / holding.amount;
+            }
+            None => self.holdings.push(NpcHolding {
+                coin: coin.id(),
+                amount,
+                entry_price: coin.current_price,
+            }),
+        }
+
+        value
+    }
+
+    /// Sells the whole holding and returns the dollars traded.
+    fn sell(&mut self, coin: &CryptoCoin) -> f64 {
+        let amount = match self.holding(coin.id()) {
+            Some(holding) => holding.amount,
+            None => return 0.0,
+        };
+
+        let value = amount * coin.current_price;
+        self.cash += value;
+        self.holdings.retain(|h| h.coin != coin.id());
+
+        value
+    }
+}
+
+/// A trade or hash move by an agent, `flow` is dollars bought (positive) or
+/// sold (negative).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NpcMove {
+    pub npc: String,
+    pub strategy: NpcStrategy,
+    pub coin: CoinId,
+    pub coin_name: String,
+    pub flow: f64,
+    pub impact: f64,
+}
+
+impl NpcMove {
+    pub fn headline(&self) -> String {
+        let verb = if self.flow < 0.0 {
+            match self.strategy {
+                NpcStrategy::PanicSeller => "panic sells",
+                NpcStrategy::Whale => "dumps",
+                _ => "sells",
+            }
+        } else {
+            match self.strategy {
+                NpcStrategy::Whale => "scoops up",
+                _ => "buys",
+            }
+        };
+
+        format!(
+            "{} {} ${:.0} of {}",
+            self.npc,
+            verb,
+            self.flow.abs(),
+            self.coin_name
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct NpcDesk {
+    pub agents: Vec<Npc>,
+    /// Net dollar flow per coin from the last update.
+    pub flows: Vec<(CoinId, f64)>,
+}
+
+impl NpcDesk {
+    pub fn new() -> Self {
+        NpcDesk {
+            agents: NPC_ROSTER
+                .iter()
+                .map(|(name, strategy)| Npc::new(name, *strategy))
+                .collect(),
+            flows: Vec::new(),
+        }
+    }
+
+    /// Lets every agent act once per market update, before prices move.
+    /// Returns the notable moves so they can be reported.
+    pub fn step(&mut self, coins: &[CryptoCoin]) -> Vec<NpcMove> {
+        let active: Vec<&CryptoCoin> = coins.iter().filter(|c| c.active).collect();
+        let mut moves = Vec::new();
+
+        for npc in &mut self.agents {
+            // Holdings in coins that left the market are gone
+            npc.holdings
+                .retain(|h| active.iter().any(|c| c.id() == h.coin));
+
+            // Busted traders come back with fresh money
+            if npc.holdings.is_empty() && npc.cash < npc.strategy.capital().start * 0.1 {
+                npc.cash = rand_from_range(npc.strategy.capital());
+            }
+
+            let trades = match npc.strategy {
+                NpcStrategy::Whale => NpcDesk::step_whale(npc, &active),
+                NpcStrategy::Momentum => NpcDesk::step_momentum(npc, &active),
+                NpcStrategy::PanicSeller => NpcDesk::step_panic_seller(npc, &active),
+                NpcStrategy::RivalMiner => {
+                    NpcDesk::step_rival_miner(npc, &active);
+                    Vec::new()
+                }
+            };
+
+            for (coin, flow) in trades {
+                moves.push(NpcMove {
+                    npc: npc.name.clone(),
+                    strategy: npc.strategy,
+                    coin: coin.id(),
+                    coin_name: coin.name.clone(),
+                    flow,
+                    impact: price_impact(coin, flow),
+                });
+            }
+        }
+
+        self.flows.clear();
+        for m in &moves {
+            match self.flows.iter_mut().find(|(coin, _)| *coin == m.coin) {
+                Some((_, flow)) => *flow += m.flow,
+                None => self.flows.push((m.coin, m.flow)),
+            }
+        }
+
+        moves
+            .into_iter()
+            .filter(|m| m.impact.abs() >= NOTABLE_IMPACT)
+            .collect()
+    }
+
+    fn step_whale<'a>(npc: &mut Npc, coins: &[&'a CryptoCoin]) -> Vec<(&'a CryptoCoin, f64)> {
+        let mut trades = Vec::new();
+
+        // Take profit or cut losses on whatever is held
+        for coin in coins {
+            if let Some(holding) = npc.holding(coin.id()) {
+                let gain = coin.current_price / holding.entry_price.max(0.0001) - 1.0;
+
+                if !(-0.25..=0.3).contains(&gain) && rand_from_range(0.0..1.0) < 0.3 {
+                    trades.push((*coin, -npc.sell(coin)));
+                }
+            }
+        }
+
+        // Now and then pick a small coin to accumulate
+        if npc.holdings.is_empty() && rand_from_range(0.0..1.0) < 0.05 {
+            let mut small: Vec<&CryptoCoin> = coins.to_vec();
+            small.sort_by(|a, b| a.market_cap().total_cmp(&b.market_cap()));
+            small.truncate(3);
+
+            if !small.is_empty() {
+                let coin = small[rand_from_range(0.0..small.len() as f64) as usize];
+                let value = npc.cash * rand_from_range(0.2..0.5);
+                trades.push((coin, npc.buy(coin, value)));
+            }
+        }
+
+        trades
+    }
+
+    fn step_momentum<'a>(npc: &mut Npc, coins: &[&'a CryptoCoin]) -> Vec<(&'a CryptoCoin, f64)> {
+        let mut trades = Vec::new();
+
+        for coin in coins {
+            let momentum = momentum(coin);
+
+            if npc.holding(coin.id()).is_some() {
+                if momentum < -0.02 {
+                    trades.push((*coin, -npc.sell(coin)));
+                }
+            } else if momentum > 0.05 && rand_from_range(0.0..1.0) < 0.5 {
+                let value = npc.cash * 0.2;
+                trades.push((*coin, npc.buy(coin, value)));
+            }
+        }
+
+        trades
+    }
+
+    fn step_panic_seller<'a>(
+        npc: &mut Npc,
+        coins: &[&'a CryptoCoin],
+    ) -> Vec<(&'a CryptoCoin, f64)> {
+        let mut trades = Vec::new();
+
+        for coin in coins {
+            if npc.holding(coin.id()).is_none() {
+                continue;
+            }
+
+            let last_move = last_change(coin);
+            let spooked = !coin.rug_signals(MAX_DUE_DILIGENCE_LEVEL).is_empty();
+
+            if last_move < -0.04 || spooked {
+                trades.push((*coin, -npc.sell(coin)));
+            }
+        }
+
+        // Slowly buys back into the market while calm
+        if npc.holdings.len() < 3 && !coins.is_empty() && rand_from_range(0.0..1.0) < 0.1 {
+            let coin = coins[rand_from_range(0.0..coins.len() as f64) as usize];
+            let value = npc.cash * 0.25;
+            trades.push((coin, npc.buy(coin, value)));
+        }
+
+        trades.retain(|(_, flow)| *flow != 0.0);
+        trades
+    }
+
+    fn step_rival_miner(npc: &mut Npc, coins: &[&CryptoCoin]) {
+        // Rivals go where the price is highest relative to the difficulty
+        let best = coins
+            .iter()
+            .filter(|c| c.blocks < c.max_blocks)
+            .max_by(|a, b| {
+                let score = |c: &CryptoCoin| c.current_price / (1.0 + c.get_difficulty());
+                score(a).total_cmp(&score(b))
+            });
+
+        if rand_from_range(0.0..1.0) < 0.2 || npc.target.is_none() {
+            npc.target = best.map(|c| c.id());
+        }
+    }
+
+    /// Net dollar flow into the coin from the last update.
+    pub fn flow_for(&self, coin: CoinId) -> f64 {
+        self.flows
+            .iter()
+            .find(|(c, _)| *c == coin)
+            .map(|(_, flow)| *flow)
+            .unwrap_or(0.0)
+    }
+
+    /// Rival hash pointed at the coin, as a multiple of the player's rig.
+    pub fn rival_hash_multiplier(&self, coin: CoinId) -> f64 {
+        self.agents
+            .iter()
+            .filter(|n| n.strategy == NpcStrategy::RivalMiner && n.target == Some(coin))
+            .map(|n| n.hash_multiplier)
+            .sum()
+    }
+
+    pub fn rivals_on(&self, coin: CoinId) -> Vec<&Npc> {
+        self.agents
+            .iter()
+            .filter(|n| n.strategy == NpcStrategy::RivalMiner && n.target == Some(coin))
+            .collect()
+    }
+}
+
+/// Price move from a dollar flow, relative to the coin's market cap.
+pub fn price_impact(coin: &CryptoCoin, flow: f64) -> f64 {
+    let cap = coin.market_cap().max(MIN_IMPACT_CAP);
+    (flow / cap).clamp(-MAX_NPC_IMPACT, MAX_NPC_IMPACT)
+}
+
+fn momentum(coin: &CryptoCoin) -> f64 {
+    let len = coin.prices.len();
+
+    if len < MOMENTUM_WINDOW {
+        return 0.0;
+    }
+
+    let start = coin.prices[len - MOMENTUM_WINDOW];
+    if start <= 0.0 {
+        return 0.0;
+    }
+    coin.current_price / start - 1.0
+}
+
+fn last_change(coin: &CryptoCoin) -> f64 {
+    let len = coin.prices.len();
+
+    if len < 2 || coin.prices[len - 2] <= 0.0 {
+        return 0.0;
+    }
+    coin.prices[len - 1] / coin.prices[len - 2] - 1.0
+}
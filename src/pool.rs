@@ -0,0 +1,266 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::utils::rand_poisson;
+
+/// A pool's hash as a multiple of the player's, so it finds blocks this many
+/// times as often as mining solo would.
+pub const POOL_SIZE: f64 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PoolScheme {
+    /// Paid the whole block and bonus when you find one yourself.
+    Solo,
+    /// Pay per share, a fixed payout for every share.
+    Pps,
+    /// Pay per last N shares, paid from your recent shares whenever the pool
+    /// finds a block.
+    Pplns,
+}
+
+impl PoolScheme {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PoolScheme::Solo => "Solo",
+            PoolScheme::Pps => "SteadyHash (PPS)",
+            PoolScheme::Pplns => "LastN Pool (PPLNS)",
+        }
+    }
+
+    pub fn fee(&self) -> f64 {
+        match self {
+            PoolScheme::Solo => 0.0,
+            PoolScheme::Pps => 0.04,
+            PoolScheme::Pplns => 0.015,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PoolScheme::Solo => "No fee and the block bonus, but nothing until you find a block.",
+            PoolScheme::Pps => "Steady payout for every share, the pool keeps the block bonus.",
+            PoolScheme::Pplns => {
+                "Lower fee, pays out your recent shares each time the pool finds a block."
+            }
+        }
+    }
+
+    pub fn all() -> Vec<PoolScheme> {
+        vec![PoolScheme::Solo, PoolScheme::Pps, PoolScheme::Pplns]
+    }
+}
+
+/// Coins paid out by a batch of shares and blocks found along the way.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PoolPayout {
+    pub paid: f64,
+    pub blocks: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolState {
+    pub scheme: PoolScheme,
+    pub accepted_shares: f64,
+    /// Recent (shares, value) batches counted for PPLNS payouts.
+    pub window: VecDeque<(f64, f64)>,
+    pub blocks_found: u64,
+    pub expected_blocks: f64,
+    pub paid: f64,
+    pub fees: f64,
+}
+
+impl PoolState {
+    pub fn new(scheme: PoolScheme) -> Self {
+        PoolState {
+            scheme,
+            accepted_shares: 0.0,
+            window: VecDeque::new(),
+            blocks_found: 0,
+            expected_blocks: 0.0,
+            paid: 0.0,
+            fees: 0.0,
+        }
+    }
+
+    /// Blocks found against the number expected, above one is lucky.
+    pub fn luck(&self) -> f64 {
+        if self.expected_blocks <= 0.0 {
+            return 1.0;
+        }
+        self.blocks_found as f64 / self.expected_blocks
+    }
+
+    /// What the next PPLNS block would pay out after fees.
+    pub fn pending(&self) -> f64 {
+        match self.scheme {
+            PoolScheme::Pplns => self.window_value() * (1.0 - self.scheme.fee()),
+            _ => 0.0,
+        }
+    }
+
+    fn window_value(&self) -> f64 {
+        self.window.iter().map(|(_, value)| value).sum()
+    }
+
+    fn window_shares(&self) -> f64 {
+        self.window.iter().map(|(shares, _)| shares).sum()
+    }
+
+    /// Books shares worth `share_value` coins each, `blocks` of which solved a
+    /// block, and returns what is paid out right now. Pool blocks follow a
+    /// Poisson draw so pool payouts have real variance.
+    pub fn submit(
+        &mut self,
+        shares: f64,
+        blocks: u64,
+        share_value: f64,
+        shares_per_block: u64,
+    ) -> PoolPayout {
+        let pool_blocks = match self.scheme {
+            PoolScheme::Solo => 0,
+            _ => rand_poisson(Self::pool_block_rate(shares, shares_per_block)),
+        };
+
+        self.settle(shares, blocks, pool_blocks, share_value, shares_per_block)
+    }
+
+    /// Blocks the whole pool is expected to find while the player finds
+    /// `shares` shares.
+    fn pool_block_rate(shares: f64, shares_per_block: u64) -> f64 {
+        shares * POOL_SIZE / shares_per_block.max(1) as f64
+    }
+
+    /// Pays out a batch once the pool's blocks have been drawn. Solo only
+    /// counts the player's own `blocks`, pools only `pool_blocks`.
+    fn settle(
+        &mut self,
+        shares: f64,
+        blocks: u64,
+        pool_blocks: u64,
+        share_value: f64,
+        shares_per_block: u64,
+    ) -> PoolPayout {
+        let per_block = shares_per_block.max(1) as f64;
+        self.accepted_shares += shares;
+
+        let fee = self.scheme.fee();
+        let (chance, blocks, gross) = match self.scheme {
+            PoolScheme::Solo => (
+                shares / per_block,
+                blocks,
+                blocks as f64 * share_value * per_block,
+            ),
+            PoolScheme::Pps => (
+                Self::pool_block_rate(shares, shares_per_block),
+                pool_blocks,
+                shares * share_value,
+            ),
+            PoolScheme::Pplns => {
+                self.window.push_back((shares, shares * share_value));
+                self.trim_window(per_block / POOL_SIZE);
+                (
+                    Self::pool_block_rate(shares, shares_per_block),
+                    pool_blocks,
+                    pool_blocks as f64 * self.window_value(),
+                )
+            }
+        };
+        let paid = gross * (1.0 - fee);
+
+        self.expected_blocks += chance;
+        self.blocks_found += blocks;
+        self.paid += paid;
+        self.fees += gross - paid;
+
+        PoolPayout { paid, blocks }
+    }
+
+    /// Drops the oldest shares so the window holds `size` shares at most.
+    fn trim_window(&mut self, size: f64) {
+        let size = size.max(1.0);
+
+        while self.window_shares() > size {
+            let excess = self.window_shares() - size;
+
+            match self.window.front_mut() {
+                Some((shares, value)) if *shares > excess => {
+                    let keep = (*shares - excess) / *shares;
+                    *shares -= excess;
+                    *value *= keep;
+                }
+                Some(_) => {
+                    self.window.pop_front();
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn solo_pays_only_its_own_blocks() {
+        let mut pool = PoolState::new(PoolScheme::Solo);
+
+        let nothing = pool.submit(50.0, 0, 0.1, 100);
+        let found = pool.submit(150.0, 1, 0.1, 100);
+
+        assert_eq!(nothing, PoolPayout::default());
+        assert_eq!(found.blocks, 1);
+        assert!(close(found.paid, 10.0));
+        assert!(close(pool.expected_blocks, 2.0));
+        assert!(close(pool.luck(), 0.5));
+        assert_eq!(pool.fees, 0.0);
+    }
+
+    #[test]
+    fn pps_pays_every_share_less_the_fee() {
+        let mut pool = PoolState::new(PoolScheme::Pps);
+
+        let payout = pool.settle(100.0, 1, 0, 0.5, 1000);
+
+        assert_eq!(payout.blocks, 0);
+        assert!(close(payout.paid, 50.0 * (1.0 - PoolScheme::Pps.fee())));
+        assert!(close(pool.fees, 50.0 * PoolScheme::Pps.fee()));
+        assert!(close(pool.expected_blocks, 2.0));
+    }
+
+    #[test]
+    fn pplns_pays_the_window_per_pool_block() {
+        let mut pool = PoolState::new(PoolScheme::Pplns);
+        let fee = PoolScheme::Pplns.fee();
+
+        let waiting = pool.settle(10.0, 0, 0, 1.0, 1000);
+        assert_eq!(waiting.paid, 0.0);
+        assert!(close(pool.pending(), 10.0 * (1.0 - fee)));
+
+        let payout = pool.settle(10.0, 0, 2, 1.0, 1000);
+        assert_eq!(payout.blocks, 2);
+        assert!(close(payout.paid, 2.0 * 20.0 * (1.0 - fee)));
+    }
+
+    #[test]
+    fn pplns_window_keeps_the_last_n_shares() {
+        let mut pool = PoolState::new(PoolScheme::Pplns);
+
+        // 1000 shares per block over a pool of 20 keeps the last 50
+        pool.settle(40.0, 0, 0, 1.0, 1000);
+        pool.settle(40.0, 0, 0, 2.0, 1000);
+
+        assert!(close(pool.window_shares(), 50.0));
+        assert!(close(pool.window_value(), 10.0 + 80.0));
+    }
+
+    #[test]
+    fn luck_is_even_before_anything_is_expected() {
+        assert_eq!(PoolState::new(PoolScheme::Pps).luck(), 1.0);
+    }
+}
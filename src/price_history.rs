@@ -0,0 +1,202 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+/// Days kept at daily resolution before folding into weeks.
+pub const MAX_DAILY_CANDLES: usize = 28;
+/// Weeks kept before the oldest are dropped, about two years of game days.
+pub const MAX_WEEKLY_CANDLES: usize = 104;
+pub const DAYS_PER_WEEK: usize = 7;
+
+/// How far back the market chart looks for a focused coin.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChartZoom {
+    Day,
+    Month,
+    Life,
+}
+
+impl ChartZoom {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChartZoom::Day => "1 Day",
+            ChartZoom::Month => "4 Weeks",
+            ChartZoom::Life => "Lifetime",
+        }
+    }
+
+    pub fn all() -> Vec<ChartZoom> {
+        vec![ChartZoom::Day, ChartZoom::Month, ChartZoom::Life]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PriceCandle {
+    pub day: u64,
+    pub days: u64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
+
+impl PriceCandle {
+    pub fn new(day: u64, price: f64) -> Self {
+        PriceCandle {
+            day,
+            days: 1,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+        }
+    }
+
+    pub fn update(&mut self, price: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+    }
+
+    /// Joins consecutive candles into one spanning all of them.
+    pub fn merge(candles: &[PriceCandle]) -> Option<PriceCandle> {
+        let first = candles.first()?;
+        let last = candles.last()?;
+
+        Some(PriceCandle {
+            day: first.day,
+            days: candles.iter().map(|c| c.days).sum(),
+            open: first.open,
+            high: candles.iter().map(|c| c.high).fold(f64::MIN, f64::max),
+            low: candles.iter().map(|c| c.low).fold(f64::MAX, f64::min),
+            close: last.close,
+        })
+    }
+}
+
+/// Price history older than the full resolution `prices`, kept as daily
+/// candles for recent weeks and weekly candles further back.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct PriceHistory {
+    pub daily: Vec<PriceCandle>,
+    pub weekly: Vec<PriceCandle>,
+}
+
+impl PriceHistory {
+    pub fn new() -> Self {
+        PriceHistory {
+            daily: Vec::new(),
+            weekly: Vec::new(),
+        }
+    }
+
+    /// Records a market update's price into the day's candle.
+    pub fn record(&mut self, day: u64, price: f64) {
+        match self.daily.last_mut() {
+            Some(candle) if candle.day == day => candle.update(price),
+            _ => self.daily.push(PriceCandle::new(day, price)),
+        }
+
+        if self.daily.len() > MAX_DAILY_CANDLES + DAYS_PER_WEEK {
+            let week: Vec<PriceCandle> = self.daily.drain(..DAYS_PER_WEEK).collect();
+
+            if let Some(candle) = PriceCandle::merge(&week) {
+                self.weekly.push(candle);
+            }
+        }
+
+        while self.weekly.len() > MAX_WEEKLY_CANDLES {
+            self.weekly.remove(0);
+        }
+    }
+
+    /// Daily candles for the last `days` days.
+    pub fn recent_days(&self, days: usize) -> Vec<PriceCandle> {
+        let skip = self.daily.len().saturating_sub(days);
+        self.daily[skip..].to_vec()
+    }
+
+    /// Every candle kept, oldest first, weekly then daily.
+    pub fn lifetime(&self) -> Vec<PriceCandle> {
+        self.weekly
+            .iter()
+            .chain(self.daily.iter())
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn updates_within_a_day_share_one_candle() {
+        let mut history = PriceHistory::new();
+        history.record(1, 10.0);
+        history.record(1, 14.0);
+        history.record(1, 8.0);
+        history.record(1, 11.0);
+
+        assert_eq!(history.daily.len(), 1);
+        let candle = history.daily[0];
+        assert_eq!(
+            (candle.open, candle.high, candle.low, candle.close),
+            (10.0, 14.0, 8.0, 11.0)
+        );
+    }
+
+    #[test]
+    fn oldest_week_folds_into_a_weekly_candle() {
+        let mut history = PriceHistory::new();
+        let days = (MAX_DAILY_CANDLES + DAYS_PER_WEEK + 1) as u64;
+
+        for day in 0..days {
+            history.record(day, day as f64 + 1.0);
+        }
+
+        assert_eq!(history.daily.len(), MAX_DAILY_CANDLES + 1);
+        assert_eq!(history.weekly.len(), 1);
+
+        let week = history.weekly[0];
+        assert_eq!(week.day, 0);
+        assert_eq!(week.days, DAYS_PER_WEEK as u64);
+        assert_eq!(
+            (week.open, week.high, week.low, week.close),
+            (1.0, 7.0, 1.0, 7.0)
+        );
+        assert_eq!(history.daily[0].day, DAYS_PER_WEEK as u64);
+    }
+
+    #[test]
+    fn weekly_candles_are_capped() {
+        let mut history = PriceHistory::new();
+        let days = (MAX_DAILY_CANDLES + DAYS_PER_WEEK * (MAX_WEEKLY_CANDLES + 3)) as u64;
+
+        for day in 0..days {
+            history.record(day, 1.0);
+        }
+
+        assert_eq!(history.weekly.len(), MAX_WEEKLY_CANDLES);
+        assert_eq!(
+            history.lifetime().len(),
+            history.weekly.len() + history.daily.len()
+        );
+    }
+
+    #[test]
+    fn recent_days_takes_the_newest_candles() {
+        let mut history = PriceHistory::new();
+        for day in 0..5 {
+            history.record(day, 1.0);
+        }
+
+        let recent = history.recent_days(2);
+        assert_eq!(recent.iter().map(|c| c.day).collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(history.recent_days(50).len(), 5);
+    }
+
+    #[test]
+    fn merging_nothing_gives_no_candle() {
+        assert_eq!(PriceCandle::merge(&[]), None);
+    }
+}
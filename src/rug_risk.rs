@@ -0,0 +1,100 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::utils::rand_from_range;
+
+pub const MAX_DUE_DILIGENCE_LEVEL: u64 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RugSignal {
+    SocialSpike,
+    ShrinkingLiquidity,
+    DevWalletOutflow,
+}
+
+impl RugSignal {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RugSignal::SocialSpike => "Social hype spike",
+            RugSignal::ShrinkingLiquidity => "Liquidity shrinking",
+            RugSignal::DevWalletOutflow => "Dev wallet outflows",
+        }
+    }
+
+    /// Due diligence level needed before the signal shows up at all.
+    pub fn required_level(&self) -> u64 {
+        match self {
+            RugSignal::SocialSpike => 0,
+            RugSignal::ShrinkingLiquidity => 1,
+            RugSignal::DevWalletOutflow => 2,
+        }
+    }
+
+    /// Fraction of the warning window left when the signal starts showing,
+    /// the harder to spot signals show up earlier.
+    pub fn reveal_at(&self) -> f64 {
+        match self {
+            RugSignal::SocialSpike => 0.4,
+            RugSignal::ShrinkingLiquidity => 0.75,
+            RugSignal::DevWalletOutflow => 1.0,
+        }
+    }
+
+    pub fn all() -> Vec<RugSignal> {
+        vec![
+            RugSignal::SocialSpike,
+            RugSignal::ShrinkingLiquidity,
+            RugSignal::DevWalletOutflow,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RugRisk {
+    pub lead: u64,
+    pub countdown: u64,
+    pub signals: Vec<RugSignal>,
+}
+
+impl RugRisk {
+    /// Schedules a rug pull one to three days (of market updates) out, with a
+    /// random set of tells leading up to it.
+    pub fn schedule() -> Self {
+        let lead = rand_from_range(96.0..288.0) as u64;
+
+        let mut signals: Vec<RugSignal> = RugSignal::all()
+            .into_iter()
+            .filter(|_| rand_from_range(0.0..1.0) < 0.7)
+            .collect();
+
+        if signals.is_empty() {
+            signals.push(RugSignal::ShrinkingLiquidity);
+        }
+
+        RugRisk {
+            lead,
+            countdown: lead,
+            signals,
+        }
+    }
+
+    /// Counts down one market update, returns true once the rug is due.
+    pub fn tick(&mut self) -> bool {
+        self.countdown = self.countdown.saturating_sub(1);
+        self.countdown == 0
+    }
+
+    pub fn visible_signals(&self, due_diligence: u64) -> Vec<RugSignal> {
+        let remaining = self.countdown as f64 / self.lead.max(1) as f64;
+
+        self.signals
+            .iter()
+            .filter(|s| due_diligence >= s.required_level() && remaining <= s.reveal_at())
+            .copied()
+            .collect()
+    }
+
+    pub fn days_left(&self) -> f64 {
+        self.countdown as f64 / 96.0
+    }
+}
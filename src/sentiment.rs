@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::market::MAX_SERIES_LENGTH;
+use crate::utils::rand_from_range;
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MarketSentiment {
+    pub index: f64,
+    pub momentum: f64,
+    pub last_return: f64,
+    pub last_shock: Option<f64>,
+    pub history: Vec<f64>,
+}
+
+impl MarketSentiment {
+    pub fn new() -> Self {
+        MarketSentiment {
+            index: 0.0,
+            momentum: 0.0,
+            last_return: 0.0,
+            last_shock: None,
+            history: Vec::new(),
+        }
+    }
+
+    /// Advances the sentiment index by one market update and returns the
+    /// market-wide return that every coin is exposed to through its beta.
+    pub fn step(&mut self) -> f64 {
+        self.momentum = self.momentum * 0.9 + rand_from_range(-0.02..0.02);
+        self.index = self.index * 0.98 + self.momentum;
+
+        // Rare crashes and rallies hit the whole board at once
+        self.last_shock = None;
+        if rand_from_range(0.0..1.0) < 0.005 {
+            let shock = if rand_from_range(0.0..1.0) < 0.55 {
+                rand_from_range(-0.12..-0.05)
+            } else {
+                rand_from_range(0.04..0.10)
+            };
+
+            self.index += shock * 5.0;
+            self.momentum = 0.0;
+            self.last_shock = Some(shock);
+        }
+
+        self.index = self.index.clamp(-1.0, 1.0);
+
+        let mut market_return = self.index * 0.004 + rand_from_range(-0.006..0.006);
+
+        if let Some(shock) = self.last_shock {
+            market_return += shock;
+        }
+
+        self.last_return = market_return;
+
+        self.history.push(self.index);
+        if self.history.len() > MAX_SERIES_LENGTH {
+            self.history.remove(0);
+        }
+
+        market_return
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self.index {
+            i if i >= 0.5 => "Euphoric",
+            i if i >= 0.15 => "Bullish",
+            i if i > -0.15 => "Neutral",
+            i if i > -0.5 => "Bearish",
+            _ => "Panic",
+        }
+    }
+}
@@ -0,0 +1,99 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+/// Share of the principal forfeited when unstaking before maturity.
+pub const EARLY_UNSTAKE_PENALTY: f64 = 0.1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum StakeTerm {
+    Short,
+    Medium,
+    Long,
+}
+
+impl StakeTerm {
+    pub fn days(&self) -> u64 {
+        match self {
+            StakeTerm::Short => 3,
+            StakeTerm::Medium => 7,
+            StakeTerm::Long => 14,
+        }
+    }
+
+    /// Base yield paid per game day, before the coin's reward multiplier.
+    pub fn daily_rate(&self) -> f64 {
+        match self {
+            StakeTerm::Short => 0.004,
+            StakeTerm::Medium => 0.007,
+            StakeTerm::Long => 0.01,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!("{} days", self.days())
+    }
+
+    pub fn all() -> Vec<StakeTerm> {
+        vec![StakeTerm::Short, StakeTerm::Medium, StakeTerm::Long]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Stake {
+    pub amount: f64,
+    pub term: StakeTerm,
+    pub daily_rate: f64,
+    pub start_day: u64,
+    pub last_paid_day: u64,
+    pub earned: f64,
+}
+
+impl Stake {
+    pub fn new(amount: f64, term: StakeTerm, yield_multiplier: f64, day: u64) -> Self {
+        Stake {
+            amount,
+            term,
+            daily_rate: term.daily_rate() * yield_multiplier,
+            start_day: day,
+            last_paid_day: day,
+            earned: 0.0,
+        }
+    }
+
+    pub fn maturity_day(&self) -> u64 {
+        self.start_day + self.term.days()
+    }
+
+    pub fn is_mature(&self, day: u64) -> bool {
+        day >= self.maturity_day()
+    }
+
+    /// Simple annual rate, a game day counted as a day.
+    pub fn apy(&self) -> f64 {
+        self.daily_rate * 365.0
+    }
+
+    /// Yield owed for the days passed since the last payout, up to maturity.
+    pub fn accrue(&mut self, day: u64) -> f64 {
+        let pay_until = day.min(self.maturity_day());
+
+        if pay_until <= self.last_paid_day {
+            return 0.0;
+        }
+
+        let days = (pay_until - self.last_paid_day) as f64;
+        let earned = self.amount * self.daily_rate * days;
+
+        self.last_paid_day = pay_until;
+        self.earned += earned;
+
+        earned
+    }
+
+    pub fn early_penalty(&self, day: u64) -> f64 {
+        if self.is_mature(day) {
+            return 0.0;
+        }
+        self.amount * EARLY_UNSTAKE_PENALTY
+    }
+}
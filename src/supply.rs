@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+use crate::market::MAX_SERIES_LENGTH;
+use crate::utils::rand_from_range;
+
+/// Market caps below this push up the rug chance, larger ones pull it down.
+pub const RUG_REFERENCE_CAP: f64 = 5_000_000.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum CoinSort {
+    Slot,
+    MarketCap,
+    Volume,
+    Price,
+    Profit,
+}
+
+impl CoinSort {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CoinSort::Slot => "Slot",
+            CoinSort::MarketCap => "Market Cap",
+            CoinSort::Volume => "24h Volume",
+            CoinSort::Price => "Price",
+            CoinSort::Profit => "$ / Min",
+        }
+    }
+
+    pub fn all() -> Vec<CoinSort> {
+        vec![
+            CoinSort::Slot,
+            CoinSort::MarketCap,
+            CoinSort::Volume,
+            CoinSort::Price,
+            CoinSort::Profit,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoinSupply {
+    pub total: f64,
+    pub premine: f64,
+    pub circulating: f64,
+    pub turnover: f64,
+    pub volume: VecDeque<f64>,
+}
+
+impl CoinSupply {
+    /// `premine` is the share of the total supply in circulation at launch,
+    /// `turnover` the share of circulating coins traded in a day.
+    pub fn new(block_reward: f64, max_blocks: u64, premine: f64, turnover: f64) -> Self {
+        let total = (block_reward * max_blocks as f64 * rand_from_range(500.0..5000.0)).floor();
+
+        CoinSupply {
+            total,
+            premine,
+            circulating: total * premine,
+            turnover,
+            volume: VecDeque::new(),
+        }
+    }
+
+    /// Releases supply as the network mines through the coin's blocks.
+    /// `progress` is how far through its emission the coin is, 0 to 1.
+    pub fn emit(&mut self, progress: f64) {
+        let target = self.total * (self.premine + (1.0 - self.premine) * progress.clamp(0.0, 1.0));
+        self.circulating = self.circulating.max(target).min(self.total);
+    }
+
+    pub fn market_cap(&self, price: f64) -> f64 {
+        self.circulating * price
+    }
+
+    /// Simulates one market update of trading. Big moves bring in traders,
+    /// `liquidity` below one thins the book out.
+    pub fn trade(&mut self, price: f64, change: f64, liquidity: f64) {
+        let per_update = self.turnover / MAX_SERIES_LENGTH as f64;
+        let activity = (1.0 + change.abs() * 20.0) * rand_from_range(0.5..1.5);
+        let volume = self.circulating * per_update * activity * liquidity * price;
+
+        self.record_volume(volume);
+    }
+
+    /// Adds a trade by the player or an NPC to the latest update's volume.
+    pub fn add_volume(&mut self, value: f64) {
+        match self.volume.back_mut() {
+            Some(last) => *last += value,
+            None => self.record_volume(value),
+        }
+    }
+
+    fn record_volume(&mut self, value: f64) {
+        self.volume.push_back(value);
+        while self.volume.len() > MAX_SERIES_LENGTH {
+            self.volume.pop_front();
+        }
+    }
+
+    /// Dollar volume over the last game day of market updates.
+    pub fn volume_24h(&self) -> f64 {
+        self.volume.iter().sum()
+    }
+
+    /// Multiplier on the rug chance, small caps are easier to pull.
+    pub fn rug_factor(&self, price: f64) -> f64 {
+        let cap = self.market_cap(price).max(1.0);
+        (RUG_REFERENCE_CAP / cap).powf(0.25).clamp(0.5, 2.0)
+    }
+}
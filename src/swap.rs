@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+use serde::{Deserialize, Serialize};
+
+use crate::crypto_coin::{CoinId, CryptoCoin};
+
+/// Share of the swapped value kept by the exchange.
+pub const SWAP_SPREAD: f64 = 0.01;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapQuote {
+    pub from: CoinId,
+    pub from_name: String,
+    pub to: CoinId,
+    pub to_name: String,
+    pub amount_in: f64,
+    pub amount_out: f64,
+    pub rate: f64,
+    pub fee: f64,
+}
+
+impl SwapQuote {
+    /// Quotes swapping `amount` of `from` into `to` at the cross rate of
+    /// their current prices, less the spread. The fee is in dollars.
+    pub fn new(from: &CryptoCoin, to: &CryptoCoin, amount: f64) -> Option<Self> {
+        if amount <= 0.0 || from.id() == to.id() || to.current_price <= 0.0 {
+            return None;
+        }
+
+        let rate = from.current_price / to.current_price * (1.0 - SWAP_SPREAD);
+        let fee = amount * from.current_price * SWAP_SPREAD;
+
+        Some(SwapQuote {
+            from: from.id(),
+            from_name: from.name.clone(),
+            to: to.id(),
+            to_name: to.name.clone(),
+            amount_in: amount,
+            amount_out: amount * rate,
+            rate,
+            fee,
+        })
+    }
+}
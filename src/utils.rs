@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use wasm_bindgen::JsCast;
 
-use crate::crypto_coin::CryptoCoin;
+use crate::crypto_coin::{CoinId, CryptoCoin};
 use crate::i_db::{get_cmd_output, set_cmd_output, CmdOutput};
 
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
@@ -345,6 +345,12 @@ pub struct BuyModal {
     pub coin: Option<CryptoCoin>,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct CoinDetailModal {
+    pub show: bool,
+    pub coin: Option<CoinId>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
 pub struct HelpModal {
     pub show: bool,
@@ -426,6 +432,32 @@ pub fn rand_from_range(range: std::ops::Range<f64>) -> f64 {
     random_float * (range.end - range.start) + range.start
 }
 
+/// Draws how many events happen when `lambda` are expected, e.g. blocks found
+/// for a batch of shares. Large means use a normal approximation.
+pub fn rand_poisson(lambda: f64) -> u64 {
+    if lambda <= 0.0 {
+        return 0;
+    }
+
+    if lambda > 30.0 {
+        let u1 = rand_from_range(f64::EPSILON..1.0);
+        let u2 = rand_from_range(0.0..1.0);
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        return (lambda + z * lambda.sqrt()).round().max(0.0) as u64;
+    }
+
+    let limit = (-lambda).exp();
+    let mut count = 0;
+    let mut product = rand_from_range(0.0..1.0);
+
+    while product > limit {
+        count += 1;
+        product *= rand_from_range(0.0..1.0);
+    }
+
+    count
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct GameTime {
     pub day: u64,
@@ -454,6 +486,28 @@ impl GameTime {
         }
     }
 
+    /// The time the given number of game minutes earlier, never before day 0.
+    pub fn minutes_ago(&self, minutes: u64) -> GameTime {
+        let now = self.day * 24 * 60 + self.hour as u64 * 60 + self.minute as u64;
+        let then = now.saturating_sub(minutes);
+
+        GameTime {
+            day: then / (24 * 60),
+            hour: ((then / 60) % 24) as u8,
+            minute: (then % 60) as u8,
+        }
+    }
+
+    /// Market chart axis label. Updates come every 15 minutes so only the
+    /// first one in each six hour block is labelled, the rest stay blank.
+    pub fn chart_label(&self) -> String {
+        if self.hour.is_multiple_of(6) && self.minute < 15 {
+            format!("D{} {:02}:00", self.day, self.hour)
+        } else {
+            String::new()
+        }
+    }
+
     fn minutes_to_midnight(&self) -> u64 {
         let current_hour = self.hour;
         let current_minute = self.minute;
@@ -551,6 +605,31 @@ pub async fn command_line_output(msg: &str) {
     set_cmd_output(&cmd_timeout).await;
 }
 
+/// Shows a browser notification if the player has granted permission.
+pub fn browser_notification(msg: &str) {
+    if web_sys::Notification::permission() != web_sys::NotificationPermission::Granted {
+        return;
+    }
+
+    let options = web_sys::NotificationOptions::new();
+    options.set_body(msg);
+
+    let _ = web_sys::Notification::new_with_options("Hash Quest", &options);
+}
+
+/// Asks the browser for notification permission, returns true if granted.
+pub async fn request_notification_permission() -> bool {
+    let promise = match web_sys::Notification::request_permission() {
+        Ok(promise) => promise,
+        Err(_) => return false,
+    };
+
+    match wasm_bindgen_futures::JsFuture::from(promise).await {
+        Ok(permission) => permission.as_string().as_deref() == Some("granted"),
+        Err(_) => false,
+    }
+}
+
 pub fn truncate_price(value: f64) -> f64 {
     let factor = 10f64.powi(5); // 10^5 = 100000
     (value * factor).round() / factor